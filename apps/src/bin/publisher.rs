@@ -2,13 +2,18 @@ use std::str::FromStr;
 
 use alloy_primitives::{Address, Bytes, U256};
 use alloy_sol_types::{sol, SolCall};
-use anyhow::Result;
-use apps::{HostContext, TxSender};
+use anyhow::{ensure, Context, Result};
+use apps::{call_latest, vote::Vote, HostContext, RiscVotingProtocolConfig, TxSender};
 use aragon_zk_voting_protocol_methods::VOTING_PROTOCOL_ELF;
 use clap::Parser;
 use risc0_ethereum_contracts::groth16::encode;
-use risc0_steel::{config::ETH_SEPOLIA_CHAIN_SPEC, ethereum::EthEvmEnv, Contract};
+use risc0_steel::{ethereum::EthEvmEnv, Contract, EvmBlockHeader};
 use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts, VerifierContext};
+use strategies::{
+    chain_spec::{supported_chain_ids, supported_chain_spec},
+    signature::verify_signature,
+    AccountHints,
+};
 use tracing_subscriber::EnvFilter;
 
 sol! {
@@ -19,6 +24,7 @@ sol! {
     }
     interface ConfigContract {
         function getVotingProtocolConfig() external view returns (string memory);
+        function assetSnapshots(uint256 proposalId, uint64 chainId) external view returns (uint256 blockNumber, bytes32 blockHash);
     }
 }
 
@@ -28,7 +34,7 @@ sol!("../contracts/IMajorityVoting.sol");
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
-    /// Ethereum chain ID
+    /// Ethereum chain ID, selecting the chain specification the proof is generated for
     #[clap(long)]
     chain_id: u64,
 
@@ -44,6 +50,11 @@ struct Args {
     #[clap(long)]
     block_number: Option<u64>,
 
+    /// Other chain holding assets of the DAO, as `<CHAIN_ID>[@<BLOCK_NUMBER>]=<RPC_URL>`. The block
+    /// defaults to the snapshot block of the chain recorded for the proposal
+    #[clap(long)]
+    asset_chain: Vec<AssetChain>,
+
     /// Voter's signature
     #[clap(long)]
     voter_signature: String,
@@ -68,6 +79,14 @@ struct Args {
     #[clap(long)]
     balance: U256,
 
+    /// Vote sequence number of the voter for the proposal
+    #[clap(long)]
+    nonce: U256,
+
+    /// Timestamp after which the vote can no longer be submitted
+    #[clap(long)]
+    deadline: u64,
+
     /// Counter's contract address on Ethereum
     #[clap(long)]
     config_contract: Address,
@@ -79,6 +98,64 @@ struct Args {
     /// Additional delegation data
     #[clap(long)]
     additional_delegation_data: String,
+
+    /// Hint of the voting power strategy of an asset for an account, replacing the one computed
+    /// by the host, as `<ASSET_INDEX>:<ACCOUNT>=<HEX_DATA>`
+    #[clap(long)]
+    voting_power_hint: Vec<VotingPowerHint>,
+}
+
+/// Chain the assets of a DAO can live on, in addition to the plugin's one.
+#[derive(Clone, Debug)]
+struct AssetChain {
+    chain_id: u64,
+    block_number: Option<u64>,
+    rpc_url: String,
+}
+
+impl FromStr for AssetChain {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (chain, rpc_url) = s
+            .split_once('=')
+            .context("expected <CHAIN_ID>[@<BLOCK_NUMBER>]=<RPC_URL>")?;
+        let (chain_id, block_number) = match chain.split_once('@') {
+            Some((chain_id, block_number)) => (chain_id, Some(block_number.parse()?)),
+            None => (chain, None),
+        };
+        Ok(AssetChain {
+            chain_id: chain_id.parse()?,
+            block_number,
+            rpc_url: rpc_url.to_string(),
+        })
+    }
+}
+
+/// Hint given to the voting power strategy of an asset, such as the token ids held by an account.
+#[derive(Clone, Debug)]
+struct VotingPowerHint {
+    asset_index: usize,
+    account: Address,
+    data: Bytes,
+}
+
+impl FromStr for VotingPowerHint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (key, data) = s
+            .split_once('=')
+            .context("expected <ASSET_INDEX>:<ACCOUNT>=<HEX_DATA>")?;
+        let (asset_index, account) = key
+            .split_once(':')
+            .context("expected <ASSET_INDEX>:<ACCOUNT>=<HEX_DATA>")?;
+        Ok(VotingPowerHint {
+            asset_index: asset_index.parse()?,
+            account: account.parse()?,
+            data: data.parse()?,
+        })
+    }
 }
 
 fn to_hex_string(bytes: &[u8]) -> String {
@@ -95,11 +172,20 @@ fn main() -> Result<()> {
     // parse the command line arguments
     let args = Args::parse();
 
+    let runtime = tokio::runtime::Runtime::new()?;
+
     // Create an EVM environment from an RPC endpoint and a block number. If no block number is
     // provided, the latest block is used.
     let mut env = EthEvmEnv::from_rpc(&args.rpc_url, args.block_number)?;
     //  The `with_chain_spec` method is used to specify the chain configuration.
-    env = env.with_chain_spec(&ETH_SEPOLIA_CHAIN_SPEC);
+    let chain_spec = supported_chain_spec(args.chain_id).with_context(|| {
+        format!(
+            "chain {} is not supported, expected one of {:?}",
+            args.chain_id,
+            supported_chain_ids()
+        )
+    })?;
+    env = env.with_chain_spec(chain_spec);
 
     // Making the preflighs. This step is mandatory
     let config_call = ConfigContract::getVotingProtocolConfigCall {};
@@ -107,41 +193,123 @@ fn main() -> Result<()> {
     let config_returns = config_contract.call_builder(&config_call).call()?;
     println!("Config string: {:?}", config_returns._0);
 
-    let config =
-        serde_json::from_str::<apps::RiscVotingProtocolConfig>(&config_returns._0).unwrap();
-    let mut strategies_context = HostContext::default(&mut env);
+    let config = config_returns._0.parse::<RiscVotingProtocolConfig>()?;
+
+    // Check the signature, preflighting the calls needed to verify contract wallets.
+    ensure!(
+        args.deadline >= env.header().timestamp(),
+        "vote deadline is before the snapshot block"
+    );
+    let digest = Vote::new(
+        args.voter,
+        args.dao_address,
+        args.proposal_id,
+        args.direction,
+        args.balance,
+        args.nonce,
+        args.deadline,
+    )
+    .signing_hash(chain_spec.chain_id(), args.config_contract);
+    verify_signature(
+        &mut env,
+        args.voter,
+        digest,
+        &Bytes::from_str(&args.voter_signature)?,
+        config.signature_validator,
+    )?;
+
+    // Create the environments of the other chains the assets live on, at the snapshot blocks
+    // recorded by the plugin when the proposal was created. The snapshots are written after the
+    // snapshot block of the proposal, so they are read at the latest block, outside of `env`.
+    let asset_envs = args
+        .asset_chain
+        .iter()
+        .map(|asset_chain| {
+            let snapshot_call = ConfigContract::assetSnapshotsCall {
+                proposalId: args.proposal_id,
+                chainId: asset_chain.chain_id,
+            };
+            let snapshot_returns = runtime.block_on(call_latest(
+                &args.rpc_url,
+                &args.config_contract.to_string(),
+                snapshot_call.abi_encode(),
+            ))?;
+            let snapshot =
+                ConfigContract::assetSnapshotsCall::abi_decode_returns(&snapshot_returns, true)?;
+            ensure!(
+                !snapshot.blockHash.is_zero(),
+                "proposal has no snapshot block on asset chain {}",
+                asset_chain.chain_id
+            );
+            let snapshot_block = snapshot.blockNumber.to::<u64>();
+            ensure!(
+                asset_chain.block_number.unwrap_or(snapshot_block) == snapshot_block,
+                "block of asset chain {} is not the snapshot block {}",
+                asset_chain.chain_id,
+                snapshot_block
+            );
+            let chain_spec = supported_chain_spec(asset_chain.chain_id).with_context(|| {
+                format!("asset chain {} is not supported", asset_chain.chain_id)
+            })?;
+            let asset_env = EthEvmEnv::from_rpc(&asset_chain.rpc_url, Some(snapshot_block))?
+                .with_chain_spec(chain_spec);
+            ensure!(
+                asset_env.block_commitment().blockHash == snapshot.blockHash,
+                "snapshot block of asset chain {} has another hash",
+                asset_chain.chain_id
+            );
+            Ok((asset_chain.chain_id, asset_env))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut strategies_context = HostContext::default(args.chain_id, env);
+    for (asset_chain_id, asset_env) in asset_envs {
+        strategies_context.add_chain(asset_chain_id, asset_env)?;
+    }
+    strategies_context.validate_params(&config)?;
     // Get the total voting power of the voter across all assets.
-    let total_voting_power: U256 = config
+    let additional_delegation_data = Bytes::from_str(args.additional_delegation_data.as_str())
+        .context("additional delegation data is not a valid hex string")?;
+    // Hints of the voting power strategies, such as the token ids held by the accounts.
+    let mut voting_power_hints = config
         .assets
         .iter()
-        .map(|asset| {
-            // Get the accounts whost voting power is delegated to the voter.
-            let delegations = strategies_context.process_delegation_strategy(
+        .enumerate()
+        .map(|(asset_index, asset)| {
+            // The hints given on the command line replace the ones computed by the strategy.
+            if args
+                .voting_power_hint
+                .iter()
+                .any(|hint| hint.asset_index == asset_index)
+            {
+                return Ok(AccountHints::new());
+            }
+            strategies_context.process_voting_power_hints(
                 args.voter,
                 asset,
-                Bytes::from_str(args.additional_delegation_data.as_str()).unwrap(),
-            );
-            if delegations.is_err() {
-                println!("Delegations given are not correct");
-                assert!(false);
-            }
-            delegations
-                .unwrap()
-                .iter()
-                .fold(U256::from(0), |acc, delegation| {
-                    (strategies_context.process_voting_power_strategy(
-                        asset.voting_power_strategy.clone(),
-                        delegation.delegate,
-                        asset,
-                    ) / delegation.ratio)
-                        + acc
-                })
-
-            // assert_eq!(asset.chain_id, destination_chain_id.chain_id());
+                additional_delegation_data.clone(),
+            )
         })
-        .sum::<U256>();
+        .collect::<Result<Vec<_>, _>>()?;
+    for hint in &args.voting_power_hint {
+        voting_power_hints
+            .get_mut(hint.asset_index)
+            .with_context(|| format!("asset {} is not in the config", hint.asset_index))?
+            .insert(hint.account, hint.data.clone());
+    }
+    // Includes the voting power delegated to the voter.
+    let total_voting_power = strategies_context
+        .process_total_voting_power(
+            &config,
+            args.voter,
+            additional_delegation_data,
+            &voting_power_hints,
+        )
+        .with_context(|| format!("voting power of {} cannot be computed", args.voter))?;
 
     println!("Total voting power: {}", total_voting_power);
+    // Take the environments back, so that they can be turned into the guest input.
+    let mut envs = strategies_context.into_envs();
     // Prepare the function call
     /*
         let call = IERC20::balanceOfCall {
@@ -162,17 +330,27 @@ fn main() -> Result<()> {
 
     println!("proving...");
 
-    let view_call_input = env.into_input()?;
+    // The environment of the plugin's chain is set at the construction of the context.
+    let view_call_input = envs.remove(&args.chain_id).unwrap().into_input()?;
+    let asset_inputs = envs
+        .into_iter()
+        .map(|(asset_chain_id, asset_env)| Ok((asset_chain_id, asset_env.into_input()?)))
+        .collect::<Result<Vec<_>>>()?;
     let env = ExecutorEnv::builder()
+        .write(&args.chain_id)?
         .write(&view_call_input)?
+        .write(&asset_inputs)?
         .write(&args.voter_signature)?
         .write(&args.voter)?
         .write(&args.dao_address)?
         .write(&args.proposal_id)?
         .write(&args.direction)?
         .write(&args.balance)?
+        .write(&args.nonce)?
+        .write(&args.deadline)?
         .write(&args.config_contract)?
         .write(&args.additional_delegation_data)?
+        .write(&voting_power_hints)?
         .build()?;
 
     let receipt = default_prover()
@@ -211,7 +389,6 @@ fn main() -> Result<()> {
     // Send the calldata to Ethereum.
     println!("sending tx...");
 
-    let runtime = tokio::runtime::Runtime::new()?;
     runtime.block_on(tx_sender.send(calldata))?;
     println!("sending tx...done");
 
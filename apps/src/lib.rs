@@ -15,109 +15,26 @@
 // The following library provides utility functions to help with sending
 // transactions to a deployed app contract on Ethereum.
 
-pub mod delegation_strategies;
-pub mod voting_power_strategies;
-use alloy_primitives::Bytes;
+pub mod vote;
 use anyhow::Result;
-use delegation_strategies::*;
 use ethers::prelude::*;
-use risc0_steel::{host::db::ProofDb, EvmBlockHeader, EvmEnv};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use voting_power_strategies::*;
+use risc0_steel::{host::db::ProofDb, EvmEnv};
+pub use strategies::{Asset, DelegationObject, RiscVotingProtocolConfig};
 
 pub(crate) type HostEvmEnv<P, H> = EvmEnv<ProofDb<P>, H>;
 
-pub struct HostContext<'a, P, H> {
-    voting_power_strategies: HashMap<String, Box<dyn VotingPowerStrategy<P, H>>>,
-    delegation_strategies: HashMap<String, Box<dyn DelegationStrategy<P, H>>>,
-    env: &'a mut HostEvmEnv<P, H>,
-}
-
-impl<'a, P, H> HostContext<'a, P, H>
-where
-    P: risc0_steel::host::provider::Provider,
-    H: EvmBlockHeader,
-{
-    pub fn default(env: &'a mut HostEvmEnv<P, H>) -> Self {
-        let mut voting_power_strategies: HashMap<String, Box<dyn VotingPowerStrategy<P, H>>> =
-            HashMap::new();
-        voting_power_strategies.insert("BalanceOf".to_string(), Box::new(BalanceOf));
-        voting_power_strategies.insert("GetPastVotes".to_string(), Box::new(GetPastVotes));
-
-        let mut delegation_strategies: HashMap<String, Box<dyn DelegationStrategy<P, H>>> =
-            HashMap::new();
-        delegation_strategies.insert("SplitDelegation".to_string(), Box::new(SplitDelegation));
-
-        Self {
-            voting_power_strategies,
-            delegation_strategies,
-            env,
-        }
-    }
-
-    pub fn add_strategy(
-        &mut self,
-        name: String,
-        voting_power_strategy: Box<dyn VotingPowerStrategy<P, H>>,
-    ) {
-        self.voting_power_strategies
-            .insert(name, voting_power_strategy);
-    }
-
-    pub fn process_voting_power_strategy(
-        &mut self,
-        name: String,
-        account: alloy_primitives::Address,
-        asset: &Asset,
-    ) -> alloy_primitives::U256 {
-        if let Some(voting_power_strategy) = self.voting_power_strategies.get(&name) {
-            voting_power_strategy.process(&mut self.env, account, asset)
-        } else {
-            panic!("Strategy not found: {}", name);
-        }
-    }
-
-    pub fn process_delegation_strategy(
-        &mut self,
-        account: alloy_primitives::Address,
-        asset: &Asset,
-        additional_data: Bytes,
-    ) -> Result<Vec<Delegation>> {
-        if let Some(delegation_strategy) = self
-            .delegation_strategies
-            .get(asset.delegation.strategy.as_str())
-        {
-            delegation_strategy.process(&mut self.env, account, asset, additional_data)
-        } else {
-            panic!("Strategy not found: {}", asset.delegation.strategy);
-        }
-    }
-}
-
-// The input of the config
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DelegationObject {
-    pub contract: alloy_primitives::Address,
-    pub strategy: String,
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Asset {
-    pub contract: alloy_primitives::Address,
-    pub chain_id: u64,
-    pub voting_power_strategy: String,
-    pub delegation: DelegationObject,
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct RiscVotingProtocolConfig {
-    pub voting_protocol_version: String,
-    pub assets: Vec<Asset>,
-    pub execution_strategy: String,
+/// Strategies context of the preflight, owning the environments which are turned into the guest
+/// input once the strategies have been applied, see [`strategies::Context::into_envs`].
+pub type HostContext<P, H> = strategies::Context<HostEvmEnv<P, H>>;
+
+/// Executes a view call at the latest block with a plain RPC call, for the state that is not
+/// proven, such as the one written after the snapshot block of the environments.
+pub async fn call_latest(rpc_url: &str, contract: &str, calldata: Vec<u8>) -> Result<Vec<u8>> {
+    let provider = Provider::<Http>::try_from(rpc_url)?;
+    let tx = TransactionRequest::new()
+        .to(contract.parse::<Address>()?)
+        .data(calldata);
+    Ok(provider.call(&tx.into(), None).await?.to_vec())
 }
 
 /// Wrapper of a `SignerMiddleware` client to send transactions to the given
@@ -0,0 +1,75 @@
+use alloy_primitives::Address;
+use serde_json::{json, Value};
+pub use strategies::vote::{Vote, EIP712_DOMAIN_NAME, EIP712_DOMAIN_VERSION};
+
+/// Builds the EIP-712 typed data of a vote, as expected by `eth_signTypedData_v4`.
+///
+/// The digest of the returned typed data matches [`Vote::signing_hash`], which is the one
+/// verified by the voting protocol guest.
+pub fn vote_typed_data(chain_id: u64, plugin: Address, vote: &Vote) -> Value {
+    json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" },
+            ],
+            "Vote": [
+                { "name": "voter", "type": "address" },
+                { "name": "dao", "type": "address" },
+                { "name": "proposalId", "type": "uint256" },
+                { "name": "direction", "type": "uint8" },
+                { "name": "balance", "type": "uint256" },
+                { "name": "nonce", "type": "uint256" },
+                { "name": "deadline", "type": "uint64" },
+            ],
+        },
+        "primaryType": "Vote",
+        "domain": {
+            "name": EIP712_DOMAIN_NAME,
+            "version": EIP712_DOMAIN_VERSION,
+            "chainId": chain_id,
+            "verifyingContract": plugin.to_string(),
+        },
+        "message": {
+            "voter": vote.voter.to_string(),
+            "dao": vote.dao.to_string(),
+            "proposalId": vote.proposalId.to_string(),
+            "direction": vote.direction,
+            "balance": vote.balance.to_string(),
+            "nonce": vote.nonce.to_string(),
+            "deadline": vote.deadline,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, B256, U256};
+    use ethers::types::transaction::eip712::{Eip712, TypedData};
+
+    #[test]
+    fn typed_data_hash_matches_signing_hash() {
+        let plugin = address!("5FbDB2315678afecb367f032d93F642f64180aa3");
+        let vote = Vote::new(
+            address!("70997970C51812dc3A010C7d01b50e0d17dc79C8"),
+            address!("e7f1725E7734CE288F8367e1Bb143E90bb3F0512"),
+            U256::from(3),
+            2,
+            U256::from(1_000_000_000_000_000_000u128),
+            U256::from(7),
+            1_700_000_000,
+        );
+
+        let typed_data: TypedData =
+            serde_json::from_value(vote_typed_data(11155111, plugin, &vote)).unwrap();
+        let typed_data_hash = typed_data.encode_eip712().unwrap();
+
+        assert_eq!(
+            B256::from(typed_data_hash),
+            vote.signing_hash(11155111, plugin)
+        );
+    }
+}
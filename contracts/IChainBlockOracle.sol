@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+pragma solidity ^0.8.17;
+
+/// @title IChainBlockOracle
+/// @notice Trusted source of the blocks of other chains, such as an L1 block oracle or a bridge.
+interface IChainBlockOracle {
+    /// @notice Returns the latest block of a chain known to the oracle.
+    /// @param chainId The ID of the chain.
+    /// @return blockNumber The number of the block.
+    /// @return blockHash The hash of the block, zero if the chain is unknown.
+    function latestBlock(
+        uint64 chainId
+    ) external view returns (uint256 blockNumber, bytes32 blockHash);
+}
@@ -13,6 +13,7 @@ import {IDAO} from "@aragon/osx/core/dao/IDAO.sol";
 import {IRiscZeroVerifier} from "risc0/IRiscZeroVerifier.sol";
 import {Steel} from "risc0/steel/Steel.sol";
 import {ImageID} from "./ImageID.sol"; // auto-generated contract after running `cargo build`.
+import {IChainBlockOracle} from "./IChainBlockOracle.sol";
 
 /// @title Counter
 /// @notice Implements a counter that increments based on off-chain Steel proofs submitted to this contract.
@@ -31,6 +32,15 @@ contract RiscVotingProtocolPlugin is MajorityVotingBase {
 
     IERC20Upgradeable public votingToken;
 
+    /// @notice Block commitment of a chain, other than this one, holding assets of the DAO.
+    /// @dev `headerFamily` is 0 for Ethereum chains and 1 for OP-stack chains, whose headers share
+    /// the encoding of the Ethereum headers.
+    struct ChainCommitment {
+        uint64 chainId;
+        uint8 headerFamily;
+        Steel.Commitment commitment;
+    }
+
     /// @notice Journal that is committed to by the guest.
     struct VotingJournal {
         Steel.Commitment commitment;
@@ -39,20 +49,55 @@ contract RiscVotingProtocolPlugin is MajorityVotingBase {
         address voter;
         uint256 balance;
         uint8 direction;
+        uint256 nonce;
+        uint64 deadline;
+        uint64 chainId;
+        uint8 headerFamily;
+        ChainCommitment[] assetCommitments;
     }
 
     struct ExecutionJournal {
         Steel.Commitment commitment;
         address configContract;
         uint256 proposalId;
-        address voter;
-        uint256 balance;
-        uint8 direction;
+        uint256[] tally;
+        uint64 chainId;
+        uint8 headerFamily;
+        ChainCommitment[] assetCommitments;
     }
 
+    /// @notice The ID of the permission required to call the `updateAssetChains` function.
+    bytes32 public constant UPDATE_ASSET_CHAINS_PERMISSION_ID =
+        keccak256("UPDATE_ASSET_CHAINS_PERMISSION");
+
     /// @notice Counter to track the number of successful verifications.
     uint256 public counter;
 
+    /// @notice Lowest vote nonce accepted for a voter on a proposal.
+    mapping(uint256 => mapping(address => uint256)) public nextVoteNonce;
+
+    /// @notice Block commitments of the other asset chains, read from the oracle when a proposal
+    /// is created.
+    mapping(uint256 => mapping(uint64 => Steel.Commitment)) public assetSnapshots;
+
+    /// @notice Trusted source of the snapshot blocks of the other asset chains.
+    IChainBlockOracle public chainBlockOracle;
+
+    /// @notice Other chains holding assets of the DAO.
+    uint64[] internal assetChainIds;
+
+    /// @notice Emitted when the other asset chains or their block oracle are updated.
+    /// @param chainBlockOracle The new block oracle.
+    /// @param assetChainIds The new asset chains.
+    event AssetChainsUpdated(
+        IChainBlockOracle chainBlockOracle,
+        uint64[] assetChainIds
+    );
+
+    /// @notice Thrown if the block oracle knows no block of an asset chain.
+    /// @param chainId The ID of the asset chain.
+    error UnknownAssetChainBlock(uint64 chainId);
+
     function initialize(
         IDAO _dao,
         VotingSettings calldata _votingSettings,
@@ -116,6 +161,7 @@ contract RiscVotingProtocolPlugin is MajorityVotingBase {
         proposal_.parameters.votingMode = votingMode();
         proposal_.parameters.supportThreshold = supportThreshold();
         proposal_.parameters.snapshotBlockHash = blockhash(snapshotBlock);
+        _snapshotAssetChains(proposalId);
 
         // Reduce costs
         if (_allowFailureMap != 0) {
@@ -140,6 +186,7 @@ contract RiscVotingProtocolPlugin is MajorityVotingBase {
             journal.configContract == address(this),
             "Invalid token address"
         );
+        require(journal.chainId == block.chainid, "Invalid chain");
 
         Proposal storage proposal_ = proposals[journal.proposalId];
 
@@ -154,10 +201,20 @@ contract RiscVotingProtocolPlugin is MajorityVotingBase {
             "Invalid commitment"
         );
 
+        require(block.timestamp <= journal.deadline, "Vote expired");
+        require(
+            journal.nonce >= nextVoteNonce[journal.proposalId][journal.voter],
+            "Vote nonce already used"
+        );
+
+        _checkAssetCommitments(journal.proposalId, journal.assetCommitments);
+
         // Verify the proof
         bytes32 journalHash = sha256(journalData);
         verifier.verify(seal, votingProtocolImageId, journalHash);
 
+        nextVoteNonce[journal.proposalId][journal.voter] = journal.nonce + 1;
+
         // The actual vote
         // This could re-enter, though we can assume the governance token is not malicious
         uint256 votingPower = journal.balance;
@@ -235,6 +292,7 @@ contract RiscVotingProtocolPlugin is MajorityVotingBase {
             executionJournal.configContract == address(this),
             "Invalid token address"
         );
+        require(executionJournal.chainId == block.chainid, "Invalid chain");
         uint256 _proposalId = executionJournal.proposalId;
         Proposal storage proposal_ = proposals[_proposalId];
 
@@ -251,6 +309,16 @@ contract RiscVotingProtocolPlugin is MajorityVotingBase {
                 proposal_.parameters.snapshotBlock,
             "Invalid commitment"
         );
+        // The tally proven against the total supply must be the one voted on this chain.
+        require(executionJournal.tally.length == 3, "Invalid tally");
+        require(
+            executionJournal.tally[0] == proposal_.tally.yes &&
+                executionJournal.tally[1] == proposal_.tally.no &&
+                executionJournal.tally[2] == proposal_.tally.abstain,
+            "Invalid tally"
+        );
+
+        _checkAssetCommitments(_proposalId, executionJournal.assetCommitments);
 
         // Verify the proof
         bytes32 journalHash = sha256(journalData);
@@ -259,9 +327,78 @@ contract RiscVotingProtocolPlugin is MajorityVotingBase {
         _execute(_proposalId);
     }
 
+    /// @notice Returns the other chains holding assets of the DAO.
+    function getAssetChainIds() external view returns (uint64[] memory) {
+        return assetChainIds;
+    }
+
+    /// @notice Updates the other chains holding assets of the DAO, and the oracle their snapshot
+    /// blocks are read from.
+    /// @dev Only proposals created afterwards are affected.
+    /// @param _chainBlockOracle The new block oracle.
+    /// @param _assetChainIds The new asset chains.
+    function updateAssetChains(
+        IChainBlockOracle _chainBlockOracle,
+        uint64[] calldata _assetChainIds
+    ) external auth(UPDATE_ASSET_CHAINS_PERMISSION_ID) {
+        chainBlockOracle = _chainBlockOracle;
+        assetChainIds = _assetChainIds;
+        emit AssetChainsUpdated(_chainBlockOracle, _assetChainIds);
+    }
+
+    /// @notice Records the snapshot block of each asset chain of a new proposal, as known to the
+    /// block oracle.
+    function _snapshotAssetChains(uint256 _proposalId) internal {
+        for (uint256 i; i < assetChainIds.length; ) {
+            uint64 chainId = assetChainIds[i];
+            (uint256 blockNumber, bytes32 blockHash) = chainBlockOracle
+                .latestBlock(chainId);
+            if (blockHash == bytes32(0)) {
+                revert UnknownAssetChainBlock(chainId);
+            }
+
+            Steel.Commitment storage snapshot = assetSnapshots[_proposalId][
+                chainId
+            ];
+            snapshot.blockNumber = blockNumber;
+            snapshot.blockHash = blockHash;
+
+            unchecked {
+                ++i;
+            }
+        }
+    }
+
+    /// @notice Checks that a proof uses the snapshot block of each asset chain of the proposal.
+    /// @dev Chains without snapshot block were not asset chains when the proposal was created.
+    function _checkAssetCommitments(
+        uint256 _proposalId,
+        ChainCommitment[] memory _commitments
+    ) internal view {
+        for (uint256 i; i < _commitments.length; ) {
+            ChainCommitment memory chainCommitment = _commitments[i];
+            Steel.Commitment storage snapshot = assetSnapshots[_proposalId][
+                chainCommitment.chainId
+            ];
+
+            require(
+                snapshot.blockHash != bytes32(0) &&
+                    snapshot.blockHash ==
+                    chainCommitment.commitment.blockHash &&
+                    snapshot.blockNumber ==
+                    chainCommitment.commitment.blockNumber,
+                "Invalid asset commitment"
+            );
+
+            unchecked {
+                ++i;
+            }
+        }
+    }
+
     // TODO: Revisit this number
     /// @dev This empty reserved space is put in place to allow future versions to add new
     /// variables without shifting down storage in the inheritance chain.
     /// https://docs.openzeppelin.com/contracts/4.x/upgradeable#storage_gaps
-    uint256[49] private __gap;
+    uint256[45] private __gap;
 }
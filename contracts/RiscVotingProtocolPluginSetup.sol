@@ -112,7 +112,7 @@ contract RiscVotingProtocolPluginSetup is PluginSetup {
 
         // Prepare permissions
         PermissionLib.MultiTargetPermission[]
-            memory permissions = new PermissionLib.MultiTargetPermission[](2);
+            memory permissions = new PermissionLib.MultiTargetPermission[](3);
 
         // Set plugin permissions to be granted.
         // Grant the list of permissions of the plugin to the DAO.
@@ -125,8 +125,16 @@ contract RiscVotingProtocolPluginSetup is PluginSetup {
                 .UPDATE_VOTING_SETTINGS_PERMISSION_ID()
         });
 
-        // Grant `EXECUTE_PERMISSION` of the DAO to the plugin.
         permissions[1] = PermissionLib.MultiTargetPermission({
+            operation: PermissionLib.Operation.Grant,
+            where: plugin,
+            who: _dao,
+            condition: PermissionLib.NO_CONDITION,
+            permissionId: votingProtocolBase.UPDATE_ASSET_CHAINS_PERMISSION_ID()
+        });
+
+        // Grant `EXECUTE_PERMISSION` of the DAO to the plugin.
+        permissions[2] = PermissionLib.MultiTargetPermission({
             operation: PermissionLib.Operation.Grant,
             where: _dao,
             who: plugin,
@@ -187,7 +195,7 @@ contract RiscVotingProtocolPluginSetup is PluginSetup {
             revert WrongHelpersArrayLength({length: helperLength});
         }
 
-        permissions = new PermissionLib.MultiTargetPermission[](2);
+        permissions = new PermissionLib.MultiTargetPermission[](3);
 
         // Set permissions to be Revoked.
         permissions[0] = PermissionLib.MultiTargetPermission({
@@ -200,6 +208,14 @@ contract RiscVotingProtocolPluginSetup is PluginSetup {
         });
 
         permissions[1] = PermissionLib.MultiTargetPermission({
+            operation: PermissionLib.Operation.Revoke,
+            where: _payload.plugin,
+            who: _dao,
+            condition: PermissionLib.NO_CONDITION,
+            permissionId: votingProtocolBase.UPDATE_ASSET_CHAINS_PERMISSION_ID()
+        });
+
+        permissions[2] = PermissionLib.MultiTargetPermission({
             operation: PermissionLib.Operation.Revoke,
             where: _dao,
             who: _payload.plugin,
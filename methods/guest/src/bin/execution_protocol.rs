@@ -3,8 +3,12 @@
 
 use alloy_primitives::{Address, U256};
 use alloy_sol_types::{sol, SolValue};
-use risc0_steel::{config::ETH_SEPOLIA_CHAIN_SPEC, ethereum::EthEvmInput, Contract, SolCommitment};
+use risc0_steel::{ethereum::EthEvmInput, Contract, SolCommitment};
 use risc0_zkvm::guest::env;
+use strategies::{
+    chain_spec::{header_family, supported_chain_spec},
+    AccountHints, ChainCommitment, RiscVotingProtocolConfig,
+};
 
 risc0_zkvm::guest::entry!(main);
 
@@ -24,23 +28,31 @@ sol! {
         address config_contract;
         uint256 proposal_id;
         uint256[] tally;
+        uint64 chain_id;
+        uint8 header_family;
+        ChainCommitment[] asset_commitments;
     }
 }
 
 fn main() {
     // Read the input from the guest environment.
     println!("Reading input from the guest environment");
+    let chain_id: u64 = env::read();
+    // OP-stack headers share the encoding of the Ethereum headers, so the inputs of every chain
+    // are decoded as Ethereum inputs, see `HeaderFamily`.
     let input: EthEvmInput = env::read();
+    let asset_inputs: Vec<(u64, EthEvmInput)> = env::read();
     let _dao: Address = env::read();
     let proposal_id: U256 = env::read();
     let tally: [U256; 3] = env::read();
     let config_contract: Address = env::read();
+    let excluded_holders_hints: Vec<AccountHints> = env::read();
 
     // Converts the input into a `EvmEnv` for execution. The `with_chain_spec` method is used
     // to specify the chain configuration. It checks that the state matches the state root in the
     // header provided in the input.
-    let destination_chain_id = &ETH_SEPOLIA_CHAIN_SPEC;
-    let env = input.into_env().with_chain_spec(destination_chain_id);
+    let chain_spec = supported_chain_spec(chain_id).expect("chain is not supported");
+    let env = input.into_env().with_chain_spec(chain_spec);
 
     let config_call = ConfigContract::getVotingProtocolConfigCall {};
     let config_returns = Contract::new(config_contract, &env)
@@ -48,28 +60,43 @@ fn main() {
         .call();
     println!("Config Returns: {:?}", config_returns._0);
 
-    let config =
-        serde_json::from_str::<strategies::RiscVotingProtocolConfig>(&config_returns._0).unwrap();
+    let config = config_returns
+        ._0
+        .parse::<RiscVotingProtocolConfig>()
+        .unwrap_or_else(|err| panic!("{}", err));
 
-    let strategies_context = strategies::Context::default(env);
+    // Assets living on other chains are read from their own environment.
+    let mut strategies_context = strategies::Context::default(chain_id, env);
+    for (asset_chain_id, asset_input) in asset_inputs {
+        let asset_chain_spec =
+            supported_chain_spec(asset_chain_id).expect("asset chain is not supported");
+        strategies_context
+            .add_chain(
+                asset_chain_id,
+                asset_input.into_env().with_chain_spec(asset_chain_spec),
+            )
+            .unwrap_or_else(|err| panic!("{}", err));
+    }
+    strategies_context
+        .validate_params(&config)
+        .unwrap_or_else(|err| panic!("{}", err));
 
     // Get the total voting power of the voter across all assets.
-    let total_voting_power = config
-        .assets
-        .iter()
-        .map(|asset| {
-            strategies_context.process_total_supply(asset.voting_power_strategy.clone(), asset)
-        })
-        .sum::<U256>();
+    let total_voting_power = strategies_context
+        .process_config_supply(&config, &excluded_holders_hints)
+        .unwrap_or_else(|err| panic!("{}", err));
 
     println!("Total voting power: {}", total_voting_power);
 
     // General settings constraints
-    assert!(strategies_context.process_execution_strategy(
-        config.execution_strategy,
-        total_voting_power,
-        tally
-    ));
+    assert!(strategies_context
+        .process_execution_strategy(
+            &config.execution_strategy,
+            &config.execution_params,
+            total_voting_power,
+            tally
+        )
+        .unwrap_or_else(|err| panic!("{}", err)));
 
     // Commit the block hash and number used when deriving `view_call_env` to the journal.
     let journal = Journal {
@@ -77,6 +104,9 @@ fn main() {
         config_contract,
         proposal_id,
         tally: tally.to_vec(),
+        chain_id,
+        header_family: header_family(chain_id).expect("chain is not supported") as u8,
+        asset_commitments: strategies_context.asset_block_commitments(),
     };
     env::commit_slice(&journal.abi_encode());
 }
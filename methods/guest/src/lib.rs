@@ -0,0 +1,36 @@
+//! Input decoding shared by the guests, kept out of the binaries so that it can be tested on
+//! the host.
+
+use hex::FromHex;
+
+/// Decodes a hex encoded signature, with or without `0x` prefix.
+pub fn parse_signature(signature: &str) -> Vec<u8> {
+    let signature = signature.strip_prefix("0x").unwrap_or(signature);
+    Vec::from_hex(signature).expect("signature is not a valid hex string")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_signatures_with_or_without_prefix() {
+        let signature = [0x42u8; 65].to_vec();
+        let encoded = hex::encode(&signature);
+        assert_eq!(parse_signature(&encoded), signature);
+        assert_eq!(parse_signature(&format!("0x{}", encoded)), signature);
+    }
+
+    #[test]
+    #[should_panic(expected = "signature is not a valid hex string")]
+    fn rejects_malformed_hex() {
+        parse_signature("0xzz");
+    }
+
+    #[test]
+    #[should_panic(expected = "signature is not a valid hex string")]
+    fn rejects_truncated_hex() {
+        let encoded = hex::encode([0x42u8; 65]);
+        parse_signature(&encoded[..encoded.len() - 1]);
+    }
+}
@@ -38,6 +38,8 @@ async fn root(Json(payload): Json<VotingParams>) -> (StatusCode, Json<VotingResp
     println!("Proposal Id: {}", payload.proposal_id);
     println!("Direction: {}", payload.direction);
     println!("Balance: {}", payload.balance);
+    println!("Nonce: {}", payload.nonce);
+    println!("Deadline: {}", payload.deadline);
     println!("Config Contract: {}", payload.config_contract);
     println!("Token Address: {}", payload.token_address);
     println!(
@@ -68,6 +70,8 @@ async fn root(Json(payload): Json<VotingParams>) -> (StatusCode, Json<VotingResp
         .arg(format!("--proposal-id={}", payload.proposal_id))
         .arg(format!("--direction={}", payload.direction))
         .arg(format!("--balance={}", payload.balance))
+        .arg(format!("--nonce={}", payload.nonce))
+        .arg(format!("--deadline={}", payload.deadline))
         .arg(format!("--config-contract={}", payload.config_contract))
         .arg(format!("--token={}", payload.token_address))
         .arg(format!(
@@ -109,6 +113,8 @@ struct VotingParams {
     proposal_id: String,
     direction: u8,
     balance: String,
+    nonce: String,
+    deadline: u64,
     config_contract: String,
     token_address: String,
     additional_delegation_data: String,
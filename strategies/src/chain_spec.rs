@@ -0,0 +1,77 @@
+use once_cell::sync::Lazy;
+use revm::primitives::SpecId;
+use risc0_steel::config::{ChainSpec, ETH_MAINNET_CHAIN_SPEC, ETH_SEPOLIA_CHAIN_SPEC};
+
+/// The Ethereum Holesky [ChainSpec].
+pub static ETH_HOLESKY_CHAIN_SPEC: Lazy<ChainSpec> =
+    Lazy::new(|| ChainSpec::new_single(17000, SpecId::CANCUN));
+
+/// The [ChainSpec] of a local anvil chain.
+pub static ANVIL_CHAIN_SPEC: Lazy<ChainSpec> =
+    Lazy::new(|| ChainSpec::new_single(31337, SpecId::CANCUN));
+
+/// Family of the block headers of a chain, committed to the journals.
+///
+/// Since Bedrock, OP-stack headers share the RLP encoding of the Ethereum headers, so the block
+/// hash and the state root of every supported chain are verified as an Ethereum header. The
+/// family only records which kind of chain a commitment belongs to.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderFamily {
+    Ethereum = 0,
+    OpStack = 1,
+}
+
+// The OP-stack forks are not available without the `optimism` feature of revm. Since Ecotone,
+// the execution of view calls on an OP-stack chain matches the Cancun specification, as only the
+// fee accounting of transactions differs, so OP-stack chains are specified as Cancun chains.
+
+/// The OP Mainnet [ChainSpec].
+pub static OP_MAINNET_CHAIN_SPEC: Lazy<ChainSpec> =
+    Lazy::new(|| ChainSpec::new_single(10, SpecId::CANCUN));
+
+/// The OP Sepolia [ChainSpec].
+pub static OP_SEPOLIA_CHAIN_SPEC: Lazy<ChainSpec> =
+    Lazy::new(|| ChainSpec::new_single(11155420, SpecId::CANCUN));
+
+/// The Base Mainnet [ChainSpec].
+pub static BASE_MAINNET_CHAIN_SPEC: Lazy<ChainSpec> =
+    Lazy::new(|| ChainSpec::new_single(8453, SpecId::CANCUN));
+
+/// The Base Sepolia [ChainSpec].
+pub static BASE_SEPOLIA_CHAIN_SPEC: Lazy<ChainSpec> =
+    Lazy::new(|| ChainSpec::new_single(84532, SpecId::CANCUN));
+
+/// Chains the voting protocol can be proven against, with their [ChainSpec] and the
+/// [HeaderFamily] of their block headers.
+static SUPPORTED_CHAINS: [(u64, &Lazy<ChainSpec>, HeaderFamily); 8] = [
+    (1, &ETH_MAINNET_CHAIN_SPEC, HeaderFamily::Ethereum),
+    (11155111, &ETH_SEPOLIA_CHAIN_SPEC, HeaderFamily::Ethereum),
+    (17000, &ETH_HOLESKY_CHAIN_SPEC, HeaderFamily::Ethereum),
+    (31337, &ANVIL_CHAIN_SPEC, HeaderFamily::Ethereum),
+    (10, &OP_MAINNET_CHAIN_SPEC, HeaderFamily::OpStack),
+    (11155420, &OP_SEPOLIA_CHAIN_SPEC, HeaderFamily::OpStack),
+    (8453, &BASE_MAINNET_CHAIN_SPEC, HeaderFamily::OpStack),
+    (84532, &BASE_SEPOLIA_CHAIN_SPEC, HeaderFamily::OpStack),
+];
+
+fn supported_chain(
+    chain_id: u64,
+) -> Option<&'static (u64, &'static Lazy<ChainSpec>, HeaderFamily)> {
+    SUPPORTED_CHAINS.iter().find(|(id, _, _)| *id == chain_id)
+}
+
+/// Returns the IDs of the chains the voting protocol can be proven against.
+pub fn supported_chain_ids() -> Vec<u64> {
+    SUPPORTED_CHAINS.iter().map(|(id, _, _)| *id).collect()
+}
+
+/// Returns the [ChainSpec] of a supported chain, or `None` if the chain is not supported.
+pub fn supported_chain_spec(chain_id: u64) -> Option<&'static ChainSpec> {
+    supported_chain(chain_id).map(|(_, chain_spec, _)| &***chain_spec)
+}
+
+/// Returns the [HeaderFamily] of the block headers of a supported chain.
+pub fn header_family(chain_id: u64) -> Option<HeaderFamily> {
+    supported_chain(chain_id).map(|(_, _, family)| *family)
+}
@@ -1,7 +1,9 @@
-use crate::Asset;
+use crate::{
+    environment::StrategyEnv,
+    params::{no_params, StrategyParams},
+    Asset, StrategyError,
+};
 use alloy_primitives::{Address, Bytes, U256};
-use anyhow::Result;
-use risc0_steel::EvmEnv;
 use std::iter::FromIterator;
 
 pub struct Delegation {
@@ -22,14 +24,20 @@ impl FromIterator<(Address, U256)> for Delegation {
     }
 }
 
-pub trait DelegationStrategy {
+pub trait DelegationStrategy<E: StrategyEnv> {
     fn process(
         &self,
-        env: &EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
+        env: &mut E,
         account: Address,
         asset: &Asset,
         additional_data: Bytes,
-    ) -> Result<Vec<Delegation>>;
+    ) -> Result<Vec<Delegation>, StrategyError>;
+
+    /// Validates the `params` of the delegation object. Strategies without parameters keep this
+    /// default.
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        no_params(params)
+    }
 }
 
 mod split_delegation;
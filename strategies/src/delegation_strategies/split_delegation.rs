@@ -1,13 +1,10 @@
 use super::DelegationStrategy;
-use crate::Asset;
 use crate::Delegation;
+use crate::{environment::StrategyEnv, Asset, StrategyError};
+use alloy_primitives::Address;
 use alloy_primitives::Bytes;
 use alloy_primitives::U256;
-use alloy_primitives::{Address, Uint};
 use alloy_sol_types::sol;
-use anyhow::{bail, Result};
-// use risc0_steel::EvmBlockHeader;
-use risc0_steel::{Contract, EvmEnv};
 
 sol! {
     /// ERC-20 balance function signature.
@@ -21,17 +18,20 @@ sol! {
 }
 
 pub struct SplitDelegation;
-impl DelegationStrategy for SplitDelegation {
+impl<E: StrategyEnv> DelegationStrategy<E> for SplitDelegation {
     fn process(
         &self,
-        env: &EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
+        env: &mut E,
         account: Address,
         asset: &Asset,
         additional_data: Bytes,
-    ) -> Result<Vec<Delegation>> {
+    ) -> Result<Vec<Delegation>, StrategyError> {
         // Ensure the length of the input bytes is a multiple of 20
         if additional_data.len() % 20 != 0 {
-            bail!("Input byte vector is not a valid length for Address conversion");
+            return Err(StrategyError::InvalidDelegationData(format!(
+                "length {} is not a multiple of an address length",
+                additional_data.len()
+            )));
         }
 
         // Collect chunks of 20 bytes and convert them into `Address`
@@ -42,20 +42,20 @@ impl DelegationStrategy for SplitDelegation {
 
         // Confirm the delegations are valid and get each ratio
         let context = asset.contract;
-        let delegations_contract = Contract::new(asset.delegation.contract, env);
-        let account_delegates: Vec<Option<Delegation>> = delegations
+        delegations
             .iter()
             .map(|potential_delegate| {
                 let potential_delegate_delegations_call = DelegateRegistry::getDelegationCall {
                     context: context.to_string(),
                     account: *potential_delegate,
                 };
-                let potential_delegate_delegations = delegations_contract
-                    .call_builder(&potential_delegate_delegations_call)
-                    .call();
+                let potential_delegate_delegations = env.call(
+                    asset.delegation.contract,
+                    &potential_delegate_delegations_call,
+                )?;
 
                 if potential_delegate_delegations.delegations.is_empty() {
-                    return Some(Delegation {
+                    return Ok(Delegation {
                         delegate: *potential_delegate,
                         ratio: U256::from(1),
                     });
@@ -64,27 +64,29 @@ impl DelegationStrategy for SplitDelegation {
                 let total_ratios = potential_delegate_delegations
                     .delegations
                     .iter()
-                    .fold(U256::from(0), |acc, d| acc + d.ratio);
+                    .try_fold(U256::from(0), |acc, d| acc.checked_add(d.ratio))
+                    .ok_or(StrategyError::ArithmeticOverflow)?;
 
                 // if potential_delegate_delegations.expirationTimestamp >= Uint::<256, 4>::from(env.header().timestamp())
 
-                // Find the matching delegation for the account and return a Some(Delegation) if valid
-                potential_delegate_delegations
+                // Find the matching delegation for the account
+                let delegation = potential_delegate_delegations
                     .delegations
                     .iter()
                     .find(|d| compare_bytes32_to_address(d.delegate, account))
-                    .map(|d| Delegation {
-                        delegate: *potential_delegate,
-                        ratio: total_ratios / d.ratio,
-                    })
+                    .ok_or(StrategyError::DelegationNotFound(*potential_delegate))?;
+                let ratio = total_ratios.checked_div(delegation.ratio).ok_or_else(|| {
+                    StrategyError::InvalidDelegationData(format!(
+                        "delegation from {} has a zero ratio",
+                        potential_delegate
+                    ))
+                })?;
+                Ok(Delegation {
+                    delegate: *potential_delegate,
+                    ratio,
+                })
             })
-            .collect();
-
-        if account_delegates.iter().any(|d| d.is_none()) {
-            bail!("One or more delegations are invalid");
-        } else {
-            Ok(account_delegates.into_iter().map(|d| d.unwrap()).collect())
-        }
+            .collect()
     }
 }
 
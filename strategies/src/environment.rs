@@ -0,0 +1,215 @@
+use crate::StrategyError;
+use alloy_primitives::{keccak256, Address, Bytes, U256};
+use alloy_sol_types::{abi::token::PackedSeqToken, sol_data, SolCall};
+use revm::primitives::KECCAK_EMPTY;
+use risc0_steel::{Contract, EvmBlockHeader, EvmEnv, SolCommitment, StateDb};
+
+/// Environment the strategies are evaluated in.
+///
+/// The same strategies run in the guest, against the state proven by the input, and in the host,
+/// where the calls are preflighted against an RPC node to build that input. Implementing the
+/// strategies once against this trait guarantees that both sides read the same state.
+pub trait StrategyEnv {
+    type Header: EvmBlockHeader;
+
+    /// Executes a view call at the block of the environment.
+    fn call<C: SolCall>(&mut self, address: Address, call: &C) -> Result<C::Return, StrategyError>;
+
+    /// Executes a view call whose calldata is encoded at runtime, and returns its return data
+    /// undecoded.
+    fn call_raw(&mut self, address: Address, calldata: Bytes) -> Result<Bytes, StrategyError> {
+        self.call(address, &RawCall(calldata))
+    }
+
+    /// Reads a storage slot of an account at the block of the environment, zero for accounts
+    /// or slots that are not set.
+    fn storage(&mut self, address: Address, slot: U256) -> Result<U256, StrategyError>;
+
+    /// Returns whether the account has code at the block of the environment.
+    fn has_code(&mut self, address: Address) -> Result<bool, StrategyError>;
+
+    fn header(&self) -> &Self::Header;
+
+    fn block_commitment(&self) -> SolCommitment;
+}
+
+/// Call of a function known at runtime only, as the calldata of the call, selector included.
+///
+/// Steel encodes a call with [`SolCall::abi_encode`] and decodes its return data with
+/// [`SolCall::abi_decode_returns`], which are implemented to pass the bytes through.
+struct RawCall(Bytes);
+
+impl SolCall for RawCall {
+    type Parameters<'a> = (sol_data::Bytes,);
+    type Token<'a> = (PackedSeqToken<'a>,);
+    type Return = Bytes;
+    type ReturnTuple<'a> = ();
+    type ReturnToken<'a> = ();
+
+    const SIGNATURE: &'static str = "";
+    const SELECTOR: [u8; 4] = [0; 4];
+
+    fn new((calldata,): (Bytes,)) -> Self {
+        Self(calldata)
+    }
+
+    fn tokenize(&self) -> Self::Token<'_> {
+        (PackedSeqToken(&self.0),)
+    }
+
+    fn abi_encoded_size(&self) -> usize {
+        self.0.len().saturating_sub(4)
+    }
+
+    fn abi_encode_raw(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.0.get(4..).unwrap_or_default());
+    }
+
+    fn abi_encode(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    fn abi_decode_returns(data: &[u8], _validate: bool) -> alloy_sol_types::Result<Bytes> {
+        Ok(Bytes::copy_from_slice(data))
+    }
+}
+
+/// Guest environment, whose calls are executed against the proven state.
+impl<H: EvmBlockHeader> StrategyEnv for EvmEnv<StateDb, H> {
+    type Header = H;
+
+    fn call<C: SolCall>(&mut self, address: Address, call: &C) -> Result<C::Return, StrategyError> {
+        Ok(Contract::new(address, &*self).call_builder(call).call())
+    }
+
+    /// Reads the slot from the storage trie of the account, proven against its storage root.
+    fn storage(&mut self, address: Address, slot: U256) -> Result<U256, StrategyError> {
+        let db = self.db();
+        let Some(account) = db.account(address) else {
+            return Ok(U256::from(0));
+        };
+        let storage_trie = db.storage_trie(&account.storage_root).ok_or_else(|| {
+            StrategyError::Call(anyhow::anyhow!(
+                "storage of {} is not in the input",
+                address
+            ))
+        })?;
+        let value: Option<U256> = storage_trie
+            .get_rlp(keccak256(slot.to_be_bytes::<32>()))
+            .map_err(|err| {
+                StrategyError::Call(anyhow::anyhow!("invalid storage value: {}", err))
+            })?;
+        Ok(value.unwrap_or_default())
+    }
+
+    fn has_code(&mut self, address: Address) -> Result<bool, StrategyError> {
+        let account = self.db().account(address);
+        Ok(account.is_some_and(|account| account.code_hash != KECCAK_EMPTY))
+    }
+
+    fn header(&self) -> &H {
+        EvmEnv::header(self)
+    }
+
+    fn block_commitment(&self) -> SolCommitment {
+        EvmEnv::block_commitment(self)
+    }
+}
+
+/// Host environment, whose calls are preflighted to record the state needed by the guest.
+#[cfg(feature = "host")]
+impl<P, H> StrategyEnv for EvmEnv<risc0_steel::host::db::ProofDb<P>, H>
+where
+    P: risc0_steel::host::provider::Provider,
+    H: EvmBlockHeader,
+    risc0_steel::host::db::ProofDb<P>: revm::Database,
+    <risc0_steel::host::db::ProofDb<P> as revm::Database>::Error: std::fmt::Display,
+{
+    type Header = H;
+
+    fn call<C: SolCall>(&mut self, address: Address, call: &C) -> Result<C::Return, StrategyError> {
+        Contract::preflight(address, self)
+            .call_builder(call)
+            .call()
+            .map_err(StrategyError::Call)
+    }
+
+    /// Reads the slot through the proof database, which records it to be proven in the input.
+    fn storage(&mut self, address: Address, slot: U256) -> Result<U256, StrategyError> {
+        revm::Database::storage(self.db_mut(), address, slot)
+            .map_err(|err| StrategyError::Call(anyhow::anyhow!("{}", err)))
+    }
+
+    /// Reads the account through the proof database, which records it to be proven in the input.
+    fn has_code(&mut self, address: Address) -> Result<bool, StrategyError> {
+        let info = revm::Database::basic(self.db_mut(), address)
+            .map_err(|err| StrategyError::Call(anyhow::anyhow!("{}", err)))?;
+        Ok(info.is_some_and(|info| !info.is_empty_code_hash()))
+    }
+
+    fn header(&self) -> &H {
+        EvmEnv::header(self)
+    }
+
+    fn block_commitment(&self) -> SolCommitment {
+        EvmEnv::block_commitment(self)
+    }
+}
+
+/// Environment of a block whose only contracts answer the calls mocked by the tests, by address
+/// and calldata, and whose storage is zero outside of the mocked slots.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MockEnv {
+    returns: std::collections::HashMap<(Address, Bytes), Bytes>,
+    storage: std::collections::HashMap<(Address, U256), U256>,
+}
+
+#[cfg(test)]
+impl MockEnv {
+    /// Mocks the ABI encoded `returns` of a call.
+    pub(crate) fn mock_call<C: SolCall>(&mut self, address: Address, call: &C, returns: Vec<u8>) {
+        self.returns
+            .insert((address, call.abi_encode().into()), returns.into());
+    }
+
+    /// Mocks the value of a storage slot.
+    pub(crate) fn mock_storage(&mut self, address: Address, slot: U256, value: U256) {
+        self.storage.insert((address, slot), value);
+    }
+}
+
+#[cfg(test)]
+impl StrategyEnv for MockEnv {
+    type Header = risc0_steel::ethereum::EthBlockHeader;
+
+    fn call<C: SolCall>(&mut self, address: Address, call: &C) -> Result<C::Return, StrategyError> {
+        let returns = self
+            .returns
+            .get(&(address, call.abi_encode().into()))
+            .ok_or_else(|| {
+                StrategyError::Call(anyhow::anyhow!("call to {} is not mocked", address))
+            })?;
+        C::abi_decode_returns(returns, true).map_err(|err| StrategyError::Call(err.into()))
+    }
+
+    fn storage(&mut self, address: Address, slot: U256) -> Result<U256, StrategyError> {
+        Ok(self
+            .storage
+            .get(&(address, slot))
+            .copied()
+            .unwrap_or_default())
+    }
+
+    fn has_code(&mut self, _address: Address) -> Result<bool, StrategyError> {
+        Ok(false)
+    }
+
+    fn header(&self) -> &Self::Header {
+        unimplemented!("the mock environment has no block")
+    }
+
+    fn block_commitment(&self) -> SolCommitment {
+        unimplemented!("the mock environment has no block")
+    }
+}
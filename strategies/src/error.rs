@@ -0,0 +1,57 @@
+use crate::strategy_id::StrategyId;
+use alloy_primitives::Address;
+use thiserror::Error;
+
+/// Reasons for which the strategies of a DAO cannot be applied.
+#[derive(Debug, Error)]
+pub enum StrategyError {
+    #[error("unknown strategy `{id}`, expected one of: {}", join_ids(.expected))]
+    UnknownStrategy {
+        id: StrategyId,
+        expected: Vec<StrategyId>,
+    },
+    #[error("environment not found for chain: {0}")]
+    UnknownChain(u64),
+    #[error("environment already set for chain: {0}")]
+    DuplicateChain(u64),
+    #[error("invalid delegation data: {0}")]
+    InvalidDelegationData(String),
+    #[error("delegation not found from delegate: {0}")]
+    DelegationNotFound(Address),
+    #[error("invalid strategy parameters: {0}")]
+    InvalidParams(String),
+    #[error("invalid hint: {0}")]
+    InvalidHint(String),
+    #[error("excluded holder cannot vote: {0}")]
+    ExcludedHolder(Address),
+    #[error("arithmetic overflow")]
+    ArithmeticOverflow,
+    #[error("{0}")]
+    InvalidSignature(&'static str),
+    #[error("config cannot be parsed: {0}")]
+    ConfigParse(#[from] serde_json::Error),
+    #[error("view call failed: {0:#}")]
+    Call(anyhow::Error),
+}
+
+impl StrategyError {
+    /// Error of a strategy that is not among the `registered` ones, which it lists sorted.
+    pub(crate) fn unknown_strategy<'a>(
+        id: &StrategyId,
+        registered: impl IntoIterator<Item = &'a StrategyId>,
+    ) -> Self {
+        let mut expected: Vec<StrategyId> = registered.into_iter().cloned().collect();
+        expected.sort_by_key(|id| id.to_string());
+        Self::UnknownStrategy {
+            id: id.clone(),
+            expected,
+        }
+    }
+}
+
+fn join_ids(ids: &[StrategyId]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,13 +1,24 @@
+use crate::{
+    environment::StrategyEnv,
+    params::{no_params, StrategyParams},
+    StrategyError,
+};
 use alloy_primitives::U256;
-use risc0_steel::EvmEnv;
 
-pub trait ProtocolExecutionStrategy {
+pub trait ProtocolExecutionStrategy<E: StrategyEnv> {
     fn proof_execution(
         &self,
-        env: &EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
+        env: &mut E,
+        params: &StrategyParams,
         total_supply: U256,
         tally: [U256; 3],
-    ) -> bool;
+    ) -> Result<bool, StrategyError>;
+
+    /// Validates the `executionParams` of the config. Strategies without parameters keep this
+    /// default.
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        no_params(params)
+    }
 }
 
 mod majority_voting;
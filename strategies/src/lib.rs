@@ -1,125 +1,474 @@
+pub mod chain_spec;
 pub mod delegation_strategies;
+pub mod environment;
+mod error;
 pub mod execution_strategies;
+pub mod params;
+pub mod signature;
+pub mod strategy_id;
+pub mod vote;
 pub mod voting_strategies;
 
 use alloy_primitives::{Address, Bytes, U256};
-use anyhow::{bail, Result};
+use alloy_sol_types::sol;
+use chain_spec::header_family;
 use delegation_strategies::*;
+use environment::StrategyEnv;
+pub use error::StrategyError;
 use execution_strategies::*;
-use risc0_steel::{EvmEnv, SolCommitment};
+use params::StrategyParams;
+use risc0_steel::SolCommitment;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use strategy_id::StrategyId;
 use voting_strategies::*;
 
-pub struct Context {
-    voting_power_strategies: HashMap<String, Box<dyn VotingPowerStrategy>>,
-    delegation_strategies: HashMap<String, Box<dyn DelegationStrategy>>,
-    execution_strategies: HashMap<String, Box<dyn ProtocolExecutionStrategy>>,
-    env: EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
+sol! {
+    /// Block commitment of the environment of an asset chain, with the
+    /// [`HeaderFamily`](chain_spec::HeaderFamily) of the chain.
+    struct ChainCommitment {
+        uint64 chainId;
+        uint8 headerFamily;
+        SolCommitment commitment;
+    }
+}
+
+/// Decimals to which the voting powers of the assets are normalized, see [`Asset::weigh`].
+pub const VOTING_POWER_DECIMALS: u8 = 18;
+
+/// Hints of the accounts whose voting power is counted on an asset, see
+/// [`VotingPowerStrategy::hint`].
+pub type AccountHints = HashMap<Address, Bytes>;
+
+/// Registry of the strategies, and environments of the chains the assets of a DAO live on.
+///
+/// The context is shared by the guest and by the host preflight, see [`StrategyEnv`].
+pub struct Context<E> {
+    voting_power_strategies: VotingPowerStrategies<E>,
+    delegation_strategies: HashMap<StrategyId, Box<dyn DelegationStrategy<E>>>,
+    execution_strategies: HashMap<StrategyId, Box<dyn ProtocolExecutionStrategy<E>>>,
+    /// Chain of the plugin, whose environment is used by the execution strategies.
+    chain_id: u64,
+    envs: HashMap<u64, E>,
 }
 
-impl Context {
-    pub fn new(env: EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>) -> Self {
+impl<E: StrategyEnv> Context<E> {
+    pub fn new(chain_id: u64, env: E) -> Self {
         Self {
-            voting_power_strategies: HashMap::new(),
+            voting_power_strategies: VotingPowerStrategies::default(),
             delegation_strategies: HashMap::new(),
             execution_strategies: HashMap::new(),
-            env,
+            chain_id,
+            envs: HashMap::from([(chain_id, env)]),
         }
     }
 
-    pub fn default(
-        env: EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
-    ) -> Self {
-        let mut voting_power_strategies: HashMap<String, Box<dyn VotingPowerStrategy>> =
-            HashMap::new();
-        voting_power_strategies.insert("BalanceOf".to_string(), Box::new(BalanceOf));
-        voting_power_strategies.insert("GetPastVotes".to_string(), Box::new(GetPastVotes));
+    pub fn default(chain_id: u64, env: E) -> Self
+    where
+        E: 'static,
+    {
+        let voting_power_strategies = VotingPowerStrategies::default();
+        for (name, version) in voting_strategies::REGISTERED {
+            let id = StrategyId::new(*name, *version);
+            if let Some(strategy) = voting_strategies::registered(&id, &voting_power_strategies) {
+                voting_power_strategies.borrow_mut().insert(id, strategy);
+            }
+        }
 
-        let mut delegation_strategies: HashMap<String, Box<dyn DelegationStrategy>> =
+        let mut delegation_strategies: HashMap<StrategyId, Box<dyn DelegationStrategy<E>>> =
             HashMap::new();
-        delegation_strategies.insert("SplitDelegation".to_string(), Box::new(SplitDelegation));
+        delegation_strategies.insert(
+            StrategyId::new("SplitDelegation", 1),
+            Box::new(SplitDelegation),
+        );
 
-        let mut execution_strategies: HashMap<String, Box<dyn ProtocolExecutionStrategy>> =
+        let mut execution_strategies: HashMap<StrategyId, Box<dyn ProtocolExecutionStrategy<E>>> =
             HashMap::new();
-        execution_strategies.insert("MajorityVoting".to_string(), Box::new(MajorityVoting));
+        execution_strategies.insert(
+            StrategyId::new("MajorityVoting", 1),
+            Box::new(MajorityVoting),
+        );
 
         Self {
             voting_power_strategies,
             delegation_strategies,
             execution_strategies,
-            env,
+            chain_id,
+            envs: HashMap::from([(chain_id, env)]),
         }
     }
 
-    pub fn add_strategy(&mut self, name: String, protocol_strategy: Box<dyn VotingPowerStrategy>) {
-        self.voting_power_strategies.insert(name, protocol_strategy);
+    /// Adds the environment of another chain, used by the assets living on that chain.
+    pub fn add_chain(&mut self, chain_id: u64, env: E) -> Result<(), StrategyError> {
+        if self.envs.contains_key(&chain_id) {
+            return Err(StrategyError::DuplicateChain(chain_id));
+        }
+        if header_family(chain_id).is_none() {
+            return Err(StrategyError::UnknownChain(chain_id));
+        }
+        self.envs.insert(chain_id, env);
+        Ok(())
     }
 
-    pub fn process_voting_strategy(&self, name: String, account: Address, asset: &Asset) -> U256 {
-        if let Some(protocol_strategy) = self.voting_power_strategies.get(&name) {
-            protocol_strategy.process(&self.env, account, asset)
-        } else {
-            panic!("Strategy not found: {}", name);
-        }
+    /// Registers a voting power strategy, or replaces the registered one, which the configs
+    /// validated by this context can then use, see [`Context::validate_params`].
+    pub fn add_strategy(
+        &mut self,
+        id: StrategyId,
+        protocol_strategy: Box<dyn VotingPowerStrategy<E>>,
+    ) {
+        self.voting_power_strategies
+            .borrow_mut()
+            .insert(id, protocol_strategy.into());
     }
 
-    pub fn process_total_supply(&self, name: String, asset: &Asset) -> U256 {
-        if let Some(protocol_strategy) = self.voting_power_strategies.get(&name) {
-            protocol_strategy.get_supply(&self.env, asset)
-        } else {
-            panic!("Strategy not found: {}", name);
-        }
+    pub fn process_voting_strategy(
+        &mut self,
+        id: &StrategyId,
+        account: Address,
+        asset: &Asset,
+        hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let protocol_strategy = resolve(&self.voting_power_strategies, id)?;
+        let env = chain_env(&mut self.envs, asset.chain_id)?;
+        protocol_strategy.process(env, account, asset, hint)
+    }
+
+    pub fn process_total_supply(
+        &mut self,
+        id: &StrategyId,
+        asset: &Asset,
+    ) -> Result<U256, StrategyError> {
+        let protocol_strategy = resolve(&self.voting_power_strategies, id)?;
+        let env = chain_env(&mut self.envs, asset.chain_id)?;
+        protocol_strategy.get_supply(env, asset)
     }
 
     pub fn process_delegation_strategy(
-        &self,
+        &mut self,
         account: Address,
         asset: &Asset,
         additional_data: Bytes,
-    ) -> Result<Vec<Delegation>> {
-        if let Some(delegation_strategy) = self
-            .delegation_strategies
-            .get(asset.delegation.strategy.as_str())
-        {
-            delegation_strategy.process(&self.env, account, asset, additional_data)
-        } else {
-            bail!("Strategy not found: {}", asset.delegation.strategy);
+    ) -> Result<Vec<Delegation>, StrategyError> {
+        let env = chain_env(&mut self.envs, asset.chain_id)?;
+        let id = &asset.delegation.strategy;
+        let delegation_strategy = self.delegation_strategies.get(id).ok_or_else(|| {
+            StrategyError::unknown_strategy(id, self.delegation_strategies.keys())
+        })?;
+        delegation_strategy.process(env, account, asset, additional_data)
+    }
+
+    /// Computes in the host the hints of the accounts delegating their voting power on an asset
+    /// to `account`, to be given to the guest along with `additional_data`.
+    pub fn process_voting_power_hints(
+        &mut self,
+        account: Address,
+        asset: &Asset,
+        additional_data: Bytes,
+    ) -> Result<AccountHints, StrategyError> {
+        let delegations = self.process_delegation_strategy(account, asset, additional_data)?;
+        let protocol_strategy =
+            resolve(&self.voting_power_strategies, &asset.voting_power_strategy)?;
+        let env = chain_env(&mut self.envs, asset.chain_id)?;
+        let mut hints = AccountHints::new();
+        for delegation in delegations {
+            let hint = protocol_strategy.hint(env, delegation.delegate, asset)?;
+            if !hint.is_empty() {
+                hints.insert(delegation.delegate, hint);
+            }
+        }
+        Ok(hints)
+    }
+
+    /// Returns the voting power of an account on an asset, made of the voting power delegated to
+    /// it by the accounts listed in `additional_data`, other than the `excluded_holders`. The
+    /// voting power is weighted, and capped by the cap of the asset.
+    pub fn process_voting_power(
+        &mut self,
+        account: Address,
+        asset: &Asset,
+        additional_data: Bytes,
+        hints: &AccountHints,
+        excluded_holders: &[Address],
+    ) -> Result<U256, StrategyError> {
+        let delegations = self.process_delegation_strategy(account, asset, additional_data)?;
+        let no_hint = Bytes::new();
+        delegations
+            .iter()
+            .filter(|delegation| !excluded_holders.contains(&delegation.delegate))
+            .try_fold(U256::from(0), |acc, delegation| {
+                let voting_power = self.process_voting_strategy(
+                    &asset.voting_power_strategy,
+                    delegation.delegate,
+                    asset,
+                    hints.get(&delegation.delegate).unwrap_or(&no_hint),
+                )?;
+                acc.checked_add(voting_power / delegation.ratio)
+                    .ok_or(StrategyError::ArithmeticOverflow)
+            })
+            .and_then(|voting_power| asset.weigh(voting_power))
+            .map(|voting_power| match asset.voting_power_cap {
+                Some(cap) => voting_power.min(cap),
+                None => voting_power,
+            })
+    }
+
+    /// Returns the voting power of an account across the assets of the config, given the hints
+    /// of each asset, capped by the cap of the config. The excluded holders of the config cannot
+    /// vote.
+    pub fn process_total_voting_power(
+        &mut self,
+        config: &RiscVotingProtocolConfig,
+        account: Address,
+        additional_data: Bytes,
+        hints: &[AccountHints],
+    ) -> Result<U256, StrategyError> {
+        if config.excluded_holders.contains(&account) {
+            return Err(StrategyError::ExcludedHolder(account));
+        }
+        if hints.len() != config.assets.len() {
+            return Err(StrategyError::InvalidHint(
+                "one set of voting power hints is expected per asset".to_string(),
+            ));
         }
+        config
+            .assets
+            .iter()
+            .zip(hints)
+            .try_fold(U256::from(0), |acc, (asset, hints)| {
+                let voting_power = self.process_voting_power(
+                    account,
+                    asset,
+                    additional_data.clone(),
+                    hints,
+                    &config.excluded_holders,
+                )?;
+                acc.checked_add(voting_power)
+                    .ok_or(StrategyError::ArithmeticOverflow)
+            })
+            .map(|voting_power| match config.voting_power_cap {
+                Some(cap) => voting_power.min(cap),
+                None => voting_power,
+            })
+    }
+
+    /// Returns the circulating supply of the assets of the config, weighted as their voting
+    /// powers. The voting powers of the excluded holders, given their `hints` per asset as
+    /// computed by [`VotingPowerStrategy::hint`], are subtracted from the total supply of each
+    /// asset. Caps are not applied, so the supply
+    /// bounds the sum of the voting powers.
+    ///
+    /// A missing hint only lowers the voting power of an excluded holder, which raises the
+    /// supply and makes the execution thresholds harder to reach.
+    pub fn process_config_supply(
+        &mut self,
+        config: &RiscVotingProtocolConfig,
+        hints: &[AccountHints],
+    ) -> Result<U256, StrategyError> {
+        if hints.len() != config.assets.len() {
+            return Err(StrategyError::InvalidHint(
+                "one set of excluded holders hints is expected per asset".to_string(),
+            ));
+        }
+        let no_hint = Bytes::new();
+        config
+            .assets
+            .iter()
+            .zip(hints)
+            .try_fold(U256::from(0), |acc, (asset, hints)| {
+                let supply = self.process_total_supply(&asset.voting_power_strategy, asset)?;
+                let mut excluded = U256::from(0);
+                for holder in &config.excluded_holders {
+                    let hint = hints.get(holder).unwrap_or(&no_hint);
+                    let voting_power = self.process_voting_strategy(
+                        &asset.voting_power_strategy,
+                        *holder,
+                        asset,
+                        hint,
+                    )?;
+                    excluded = excluded
+                        .checked_add(voting_power)
+                        .ok_or(StrategyError::ArithmeticOverflow)?;
+                }
+                let circulating = supply.checked_sub(excluded).ok_or_else(|| {
+                    StrategyError::InvalidParams(format!(
+                        "excluded holders have a voting power of {} on asset {}, above its \
+                         supply of {}",
+                        excluded, asset.contract, supply
+                    ))
+                })?;
+                acc.checked_add(asset.weigh(circulating)?)
+                    .ok_or(StrategyError::ArithmeticOverflow)
+            })
     }
 
     pub fn process_execution_strategy(
-        &self,
-        name: String,
+        &mut self,
+        id: &StrategyId,
+        params: &StrategyParams,
         total_supply: U256,
         tally: [U256; 3],
-    ) -> bool {
-        if let Some(execution_strategy) = self.execution_strategies.get(&name) {
-            execution_strategy.proof_execution(&self.env, total_supply, tally)
-        } else {
-            panic!("Strategy not found: {}", name);
+    ) -> Result<bool, StrategyError> {
+        let env = chain_env(&mut self.envs, self.chain_id)?;
+        let execution_strategy = self
+            .execution_strategies
+            .get(id)
+            .ok_or_else(|| StrategyError::unknown_strategy(id, self.execution_strategies.keys()))?;
+        execution_strategy.proof_execution(env, params, total_supply, tally)
+    }
+
+    /// Checks that the strategies of the config are registered, and that they accept their
+    /// parameters, before any of them is applied.
+    pub fn validate_params(&self, config: &RiscVotingProtocolConfig) -> Result<(), StrategyError> {
+        let mut excluded_holders = HashSet::new();
+        if let Some(duplicate) = config
+            .excluded_holders
+            .iter()
+            .find(|holder| !excluded_holders.insert(**holder))
+        {
+            return Err(StrategyError::InvalidParams(format!(
+                "excluded holder {} is listed more than once",
+                duplicate
+            )));
+        }
+        for asset in &config.assets {
+            let voting_power_strategy =
+                resolve(&self.voting_power_strategies, &asset.voting_power_strategy)?;
+            voting_power_strategy
+                .validate_params(&asset.params)
+                .map_err(|err| with_strategy_id(err, &asset.voting_power_strategy))?;
+
+            let delegation_strategy = self
+                .delegation_strategies
+                .get(&asset.delegation.strategy)
+                .ok_or_else(|| {
+                    StrategyError::unknown_strategy(
+                        &asset.delegation.strategy,
+                        self.delegation_strategies.keys(),
+                    )
+                })?;
+            delegation_strategy
+                .validate_params(&asset.delegation.params)
+                .map_err(|err| with_strategy_id(err, &asset.delegation.strategy))?;
         }
+
+        let execution_strategy = self
+            .execution_strategies
+            .get(&config.execution_strategy)
+            .ok_or_else(|| {
+                StrategyError::unknown_strategy(
+                    &config.execution_strategy,
+                    self.execution_strategies.keys(),
+                )
+            })?;
+        execution_strategy
+            .validate_params(&config.execution_params)
+            .map_err(|err| with_strategy_id(err, &config.execution_strategy))
     }
 
     pub fn block_commitment(&self) -> SolCommitment {
-        self.env.block_commitment()
+        // The environment of the plugin's chain is set at construction and cannot be replaced.
+        self.envs[&self.chain_id].block_commitment()
+    }
+
+    /// Returns the block commitments of the asset chains other than the plugin's one, ordered
+    /// by chain ID.
+    pub fn asset_block_commitments(&self) -> Vec<ChainCommitment> {
+        let mut commitments: Vec<ChainCommitment> = self
+            .envs
+            .iter()
+            .filter(|(chain_id, _)| **chain_id != self.chain_id)
+            .map(|(chain_id, env)| ChainCommitment {
+                chainId: *chain_id,
+                // Chains without header family are rejected by `add_chain`.
+                headerFamily: header_family(*chain_id).unwrap() as u8,
+                commitment: env.block_commitment(),
+            })
+            .collect();
+        commitments.sort_by_key(|commitment| commitment.chainId);
+        commitments
+    }
+
+    /// Returns the environments of the chains by chain ID, once the strategies are applied, for
+    /// the host to turn them into the guest input.
+    pub fn into_envs(self) -> HashMap<u64, E> {
+        self.envs
     }
 }
 
-#[derive(Serialize, Deserialize)]
+/// Prefixes the reason of invalid parameters with the strategy they are given to.
+fn with_strategy_id(err: StrategyError, id: &StrategyId) -> StrategyError {
+    match err {
+        StrategyError::InvalidParams(reason) => {
+            StrategyError::InvalidParams(format!("{}: {}", id, reason))
+        }
+        err => err,
+    }
+}
+
+/// Returns the environment of a chain, to which the strategies of its assets are applied.
+fn chain_env<E>(envs: &mut HashMap<u64, E>, chain_id: u64) -> Result<&mut E, StrategyError> {
+    envs.get_mut(&chain_id)
+        .ok_or(StrategyError::UnknownChain(chain_id))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DelegationObject {
     pub contract: Address,
-    pub strategy: String,
+    pub strategy: StrategyId,
+    /// Parameters of the delegation strategy.
+    #[serde(default)]
+    pub params: StrategyParams,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Asset {
     pub contract: Address,
     pub chain_id: u64,
-    pub voting_power_strategy: String,
+    pub voting_power_strategy: StrategyId,
+    /// Parameters of the voting power strategy.
+    #[serde(default)]
+    pub params: StrategyParams,
     pub delegation: DelegationObject,
+    /// Decimals of the voting power given by the strategy.
+    #[serde(default = "default_decimals")]
+    pub decimals: u8,
+    /// Multiplier of the voting power, once normalized to [`VOTING_POWER_DECIMALS`].
+    #[serde(default = "default_weight")]
+    pub weight: U256,
+    /// Maximum weighted voting power of an account on the asset.
+    #[serde(default)]
+    pub voting_power_cap: Option<U256>,
+}
+
+fn default_decimals() -> u8 {
+    VOTING_POWER_DECIMALS
+}
+
+fn default_weight() -> U256 {
+    U256::from(1)
+}
+
+impl Asset {
+    /// Normalizes an amount of the asset to [`VOTING_POWER_DECIMALS`], rounding down, and
+    /// multiplies it by the weight of the asset.
+    pub fn weigh(&self, amount: U256) -> Result<U256, StrategyError> {
+        let normalized = if self.decimals <= VOTING_POWER_DECIMALS {
+            U256::from(10)
+                .checked_pow(U256::from(VOTING_POWER_DECIMALS - self.decimals))
+                .and_then(|scale| amount.checked_mul(scale))
+        } else {
+            U256::from(10)
+                .checked_pow(U256::from(self.decimals - VOTING_POWER_DECIMALS))
+                .map(|scale| amount / scale)
+        };
+        normalized
+            .and_then(|normalized| normalized.checked_mul(self.weight))
+            .ok_or(StrategyError::ArithmeticOverflow)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -127,5 +476,98 @@ pub struct Asset {
 pub struct RiscVotingProtocolConfig {
     pub voting_protocol_version: String,
     pub assets: Vec<Asset>,
-    pub execution_strategy: String,
+    pub execution_strategy: StrategyId,
+    /// Parameters of the execution strategy.
+    #[serde(default)]
+    pub execution_params: StrategyParams,
+    /// ERC-6492 validator used to verify the signatures of counterfactual contract wallets.
+    #[serde(default)]
+    pub signature_validator: Option<Address>,
+    /// Maximum voting power of an account across all the assets.
+    #[serde(default)]
+    pub voting_power_cap: Option<U256>,
+    /// Holders that can never vote, such as the treasury, burn addresses and bridge escrows,
+    /// whose voting powers are not part of the circulating supply.
+    #[serde(default)]
+    pub excluded_holders: Vec<Address>,
+}
+
+impl FromStr for RiscVotingProtocolConfig {
+    type Err = StrategyError;
+
+    fn from_str(config: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(config)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use environment::MockEnv;
+
+    fn config(voting_power_strategy: &str) -> RiscVotingProtocolConfig {
+        format!(
+            r#"{{
+                "votingProtocolVersion": "1",
+                "assets": [{{
+                    "contract": "0x185Bb1cca668C474214e934028A3e4BB7A5E6525",
+                    "chainId": 1,
+                    "votingPowerStrategy": "{}",
+                    "delegation": {{
+                        "contract": "0x32Bb2dB7826cf342743fe80832Fe4DF725879C2D",
+                        "strategy": "SplitDelegation"
+                    }}
+                }}],
+                "executionStrategy": "MajorityVoting"
+            }}"#,
+            voting_power_strategy
+        )
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn accepts_registered_strategies() {
+        let context = Context::default(1, MockEnv::default());
+        assert!(context.validate_params(&config("BalanceOf")).is_ok());
+        assert!(context.validate_params(&config("BalanceOf@1")).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_strategies_listing_the_registered_ones() {
+        let context = Context::default(1, MockEnv::default());
+        let err = context.validate_params(&config("BalanceOf@2")).unwrap_err();
+        let mut expected = voting_strategies::REGISTERED
+            .iter()
+            .map(|(name, version)| format!("{}@{}", name, version))
+            .collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "unknown strategy `BalanceOf@2`, expected one of: {}",
+                expected.join(", ")
+            )
+        );
+
+        let err = context.validate_params(&config("Unknown")).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("unknown strategy `Unknown@1`, expected one of: BalanceOf@1, "));
+    }
+
+    #[test]
+    fn rejects_invalid_identifiers_in_the_config() {
+        for id in ["BalanceOf@x", ""] {
+            let err = format!(
+                r#"{{"votingProtocolVersion":"1","assets":[],"executionStrategy":"{}"}}"#,
+                id
+            )
+            .parse::<RiscVotingProtocolConfig>()
+            .err()
+            .unwrap();
+            assert!(matches!(err, StrategyError::ConfigParse(_)), "{}", id);
+            assert!(err.to_string().contains("invalid"), "{}", err);
+        }
+    }
 }
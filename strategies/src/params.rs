@@ -0,0 +1,22 @@
+use crate::StrategyError;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Parameters given to a strategy in the config, whose schema is declared by the strategy.
+pub type StrategyParams = Value;
+
+/// Deserializes the parameters of a strategy into its own parameters type.
+pub fn parse_params<T: DeserializeOwned>(params: &StrategyParams) -> Result<T, StrategyError> {
+    T::deserialize(params).map_err(|err| StrategyError::InvalidParams(err.to_string()))
+}
+
+/// Checks that no parameters are given to a strategy which does not take any.
+pub fn no_params(params: &StrategyParams) -> Result<(), StrategyError> {
+    match params {
+        Value::Null => Ok(()),
+        Value::Object(fields) if fields.is_empty() => Ok(()),
+        _ => Err(StrategyError::InvalidParams(
+            "strategy does not take parameters".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,290 @@
+//! Verification of the voters' signatures, shared by the guest and the host so that the host
+//! preflights exactly the calls the guest makes.
+
+use crate::environment::StrategyEnv;
+use crate::vote::{
+    decode_erc6492_signature, is_erc6492_signature, IUniversalSigValidator, ERC1271_MAGIC_VALUE,
+    IERC1271,
+};
+use crate::StrategyError;
+use alloy_primitives::{Address, Bytes, B256};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+/// Converts an Ethereum-convention recovery ID to the k256 RecoveryId type.
+///
+/// Only the raw (0, 1) and the `personal_sign` (27, 28) conventions are accepted. EIP-155
+/// values are transaction specific and never valid for a message signature.
+fn into_recovery_id(v: u8) -> Option<RecoveryId> {
+    match v {
+        0 | 27 => Some(0),
+        1 | 28 => Some(1),
+        _ => None,
+    }
+    .and_then(RecoveryId::from_byte)
+}
+
+/// Signer address recovery from a 65 bytes `(r, s, v)` signature.
+///
+/// This methods exists to replicate the behavior of `ecrecover` within the EVM.
+/// It can only be considered a signature validation is digest is verified to be
+/// the hash of a known message.
+///
+/// Unlike the `ecrecover` precompile, signatures with a high `s` value are rejected, as
+/// required by EIP-2, so that a signature cannot be made malleable.
+pub fn ecrecover(signature: &[u8], digest: B256) -> Result<Address, &'static str> {
+    if signature.len() != 65 {
+        return Err("signature must be 65 bytes long");
+    }
+    let recovery_id = into_recovery_id(signature[64]).ok_or("value for v is invalid")?;
+    let signature =
+        Signature::from_slice(&signature[..64]).map_err(|_| "signature encoding is invalid")?;
+    if signature.normalize_s().is_some() {
+        return Err("signature is malleable (high s value)");
+    }
+    let recovered_key =
+        VerifyingKey::recover_from_prehash(digest.as_slice(), &signature, recovery_id)
+            .map_err(|_| "signature is invalid")?;
+
+    // Calculate the Ethereum address from the uncompressed public key.
+    let encoded_key = recovered_key.to_encoded_point(/* compress = */ false);
+    Ok(Address::from_raw_public_key(&encoded_key.as_bytes()[1..]))
+}
+
+/// Verifies that the signature is an ECDSA signature of the digest by the voter, as signed by
+/// EOAs.
+pub fn verify_eoa_signature(
+    voter: Address,
+    digest: B256,
+    signature: &[u8],
+) -> Result<(), StrategyError> {
+    let signer = ecrecover(signature, digest).map_err(StrategyError::InvalidSignature)?;
+    if signer != voter {
+        return Err(StrategyError::InvalidSignature(
+            "recovered signer is not the voter",
+        ));
+    }
+    Ok(())
+}
+
+/// Calls `isValidSignature` on the voter at the block of the environment.
+fn is_valid_erc1271_signature<E: StrategyEnv>(
+    env: &mut E,
+    voter: Address,
+    digest: B256,
+    signature: Bytes,
+) -> Result<bool, StrategyError> {
+    let is_valid_signature_call = IERC1271::isValidSignatureCall {
+        hash: digest,
+        signature,
+    };
+    let returns = env.call(voter, &is_valid_signature_call)?;
+    Ok(returns.magicValue == ERC1271_MAGIC_VALUE)
+}
+
+/// Verifies that the voter signed the digest.
+///
+/// EOAs are verified with `ecrecover`. When the signature is not a valid ECDSA signature of the
+/// voter and the voter has code at the block of the environment, the voter is a contract wallet
+/// and the signature is verified through EIP-1271. Counterfactual wallets provide an ERC-6492
+/// wrapped signature, verified through the `signature_validator` of the config when set.
+pub fn verify_signature<E: StrategyEnv>(
+    env: &mut E,
+    voter: Address,
+    digest: B256,
+    signature: &[u8],
+    signature_validator: Option<Address>,
+) -> Result<(), StrategyError> {
+    if is_erc6492_signature(signature) {
+        let wrapped = decode_erc6492_signature(signature).map_err(|_| {
+            StrategyError::InvalidSignature("ERC-6492 signature encoding is invalid")
+        })?;
+        let is_valid = match signature_validator {
+            Some(validator) => {
+                let is_valid_sig_call = IUniversalSigValidator::isValidSigCall {
+                    signer: voter,
+                    hash: digest,
+                    signature: signature.to_vec().into(),
+                };
+                env.call(validator, &is_valid_sig_call)?._0
+            }
+            // Without validator, only wallets deployed at the block can be verified.
+            None => is_valid_erc1271_signature(env, voter, digest, wrapped.signature)?,
+        };
+        if !is_valid {
+            return Err(StrategyError::InvalidSignature(
+                "ERC-6492 signature is not valid for the voter",
+            ));
+        }
+        return Ok(());
+    }
+
+    match verify_eoa_signature(voter, digest, signature) {
+        Ok(()) => return Ok(()),
+        Err(err) => println!("Signature is not an ECDSA signature of the voter: {}", err),
+    }
+    if !env.has_code(voter)?
+        || !is_valid_erc1271_signature(env, voter, digest, signature.to_vec().into())?
+    {
+        return Err(StrategyError::InvalidSignature(
+            "signature is not valid for the voter",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::MockEnv;
+    use k256::ecdsa::SigningKey;
+
+    const DIGEST: B256 = B256::repeat_byte(0x42);
+
+    fn signing_key(byte: u8) -> SigningKey {
+        SigningKey::from_bytes(&[byte; 32].into()).unwrap()
+    }
+
+    fn address_of(key: &SigningKey) -> Address {
+        let encoded_key = key.verifying_key().to_encoded_point(false);
+        Address::from_raw_public_key(&encoded_key.as_bytes()[1..])
+    }
+
+    /// Signs the digest as `personal_sign` does, with `v` as 27 or 28.
+    fn sign(key: &SigningKey, digest: B256) -> Vec<u8> {
+        let (signature, recovery_id) = key.sign_prehash_recoverable(digest.as_slice()).unwrap();
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(27 + recovery_id.to_byte());
+        bytes
+    }
+
+    #[test]
+    fn recovers_the_signer() {
+        let key = signing_key(1);
+        let signature = sign(&key, DIGEST);
+        assert_eq!(ecrecover(&signature, DIGEST), Ok(address_of(&key)));
+
+        // The raw recovery ID is accepted as well.
+        let mut raw = signature.clone();
+        raw[64] -= 27;
+        assert_eq!(ecrecover(&raw, DIGEST), Ok(address_of(&key)));
+    }
+
+    #[test]
+    fn recovers_another_address_for_a_wrong_signer() {
+        let signature = sign(&signing_key(2), DIGEST);
+        assert_ne!(
+            ecrecover(&signature, DIGEST),
+            Ok(address_of(&signing_key(1)))
+        );
+
+        // A signature of another digest does not recover the signer either.
+        let signature = sign(&signing_key(1), B256::repeat_byte(0x43));
+        assert_ne!(
+            ecrecover(&signature, DIGEST),
+            Ok(address_of(&signing_key(1)))
+        );
+    }
+
+    #[test]
+    fn rejects_malleable_signatures() {
+        let key = signing_key(1);
+        let (signature, recovery_id) = key.sign_prehash_recoverable(DIGEST.as_slice()).unwrap();
+        let (r, s) = signature.split_scalars();
+        let high_s = Signature::from_scalars(r, -*s).unwrap();
+        let mut bytes = high_s.to_bytes().to_vec();
+        // The flipped signature recovers the same key with the other parity.
+        bytes.push(28 - recovery_id.to_byte());
+        assert_eq!(
+            ecrecover(&bytes, DIGEST),
+            Err("signature is malleable (high s value)")
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_signatures() {
+        let signature = sign(&signing_key(1), DIGEST);
+        assert_eq!(
+            ecrecover(&signature[..64], DIGEST),
+            Err("signature must be 65 bytes long")
+        );
+        assert_eq!(
+            ecrecover(&[], DIGEST),
+            Err("signature must be 65 bytes long")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_recovery_ids() {
+        let mut signature = sign(&signing_key(1), DIGEST);
+        signature[64] = 37;
+        assert_eq!(ecrecover(&signature, DIGEST), Err("value for v is invalid"));
+    }
+
+    #[test]
+    fn verifies_the_voter_signature() {
+        let key = signing_key(1);
+        let signature = sign(&key, DIGEST);
+        assert!(verify_eoa_signature(address_of(&key), DIGEST, &signature).is_ok());
+        assert!(verify_signature(
+            &mut MockEnv::default(),
+            address_of(&key),
+            DIGEST,
+            &signature,
+            None
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn rejects_signatures_of_another_signer() {
+        let voter = address_of(&signing_key(1));
+        let signature = sign(&signing_key(2), DIGEST);
+        assert_eq!(
+            verify_eoa_signature(voter, DIGEST, &signature)
+                .unwrap_err()
+                .to_string(),
+            "recovered signer is not the voter"
+        );
+        // EOAs have no EIP-1271 fallback.
+        assert_eq!(
+            verify_signature(&mut MockEnv::default(), voter, DIGEST, &signature, None)
+                .unwrap_err()
+                .to_string(),
+            "signature is not valid for the voter"
+        );
+    }
+
+    #[test]
+    fn rejects_malleable_and_truncated_signatures_of_the_voter() {
+        let key = signing_key(1);
+        let voter = address_of(&key);
+
+        let (signature, recovery_id) = key.sign_prehash_recoverable(DIGEST.as_slice()).unwrap();
+        let (r, s) = signature.split_scalars();
+        let mut high_s = Signature::from_scalars(r, -*s).unwrap().to_bytes().to_vec();
+        high_s.push(28 - recovery_id.to_byte());
+        assert_eq!(
+            verify_eoa_signature(voter, DIGEST, &high_s)
+                .unwrap_err()
+                .to_string(),
+            "signature is malleable (high s value)"
+        );
+
+        let signature = sign(&key, DIGEST);
+        assert_eq!(
+            verify_eoa_signature(voter, DIGEST, &signature[..64])
+                .unwrap_err()
+                .to_string(),
+            "signature must be 65 bytes long"
+        );
+
+        for signature in [high_s.as_slice(), &signature[..64]] {
+            assert_eq!(
+                verify_signature(&mut MockEnv::default(), voter, DIGEST, signature, None)
+                    .unwrap_err()
+                    .to_string(),
+                "signature is not valid for the voter"
+            );
+        }
+    }
+}
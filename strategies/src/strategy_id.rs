@@ -0,0 +1,115 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Identifier of a strategy, written `<NAME>[@<VERSION>]` in the config.
+///
+/// The version lets the semantics of a strategy change without affecting the DAOs configured
+/// with a previous one. Identifiers without suffix refer to the first version, not the latest.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StrategyId {
+    name: String,
+    version: u32,
+}
+
+impl StrategyId {
+    pub fn new(name: impl Into<String>, version: u32) -> Self {
+        Self {
+            name: name.into(),
+            version,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+impl fmt::Display for StrategyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.name, self.version)
+    }
+}
+
+impl FromStr for StrategyId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, version) = match s.split_once('@') {
+            Some((name, version)) => (
+                name,
+                version
+                    .parse()
+                    .map_err(|_| format!("invalid version in strategy identifier `{}`", s))?,
+            ),
+            None => (s, 1),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("invalid strategy name in identifier `{}`", s));
+        }
+        Ok(Self::new(name, version))
+    }
+}
+
+impl Serialize for StrategyId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes an identifier of any strategy. Whether the strategy is registered is checked
+/// against the [`crate::Context`] the config is validated with, so that the strategies added
+/// with [`crate::Context::add_strategy`] can be configured.
+impl<'de> Deserialize<'de> for StrategyId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_identifiers_without_version_as_the_first_version() {
+        assert_eq!(
+            "BalanceOf".parse::<StrategyId>(),
+            Ok(StrategyId::new("BalanceOf", 1))
+        );
+    }
+
+    #[test]
+    fn parses_versioned_identifiers() {
+        assert_eq!(
+            "BalanceOf@2".parse::<StrategyId>(),
+            Ok(StrategyId::new("BalanceOf", 2))
+        );
+        assert_eq!(StrategyId::new("BalanceOf", 2).to_string(), "BalanceOf@2");
+    }
+
+    #[test]
+    fn rejects_invalid_versions() {
+        assert_eq!(
+            "BalanceOf@x".parse::<StrategyId>(),
+            Err("invalid version in strategy identifier `BalanceOf@x`".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        assert_eq!(
+            "".parse::<StrategyId>(),
+            Err("invalid strategy name in identifier ``".to_string())
+        );
+        assert_eq!(
+            "@1".parse::<StrategyId>(),
+            Err("invalid strategy name in identifier `@1`".to_string())
+        );
+    }
+}
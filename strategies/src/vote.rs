@@ -0,0 +1,103 @@
+use alloy_primitives::{fixed_bytes, Address, Bytes, FixedBytes, B256, U256};
+use alloy_sol_types::{eip712_domain, sol, Eip712Domain, SolStruct, SolValue};
+use anyhow::{anyhow, Result};
+
+/// Name of the EIP-712 signing domain of the voting protocol.
+pub const EIP712_DOMAIN_NAME: &str = "RiscVotingProtocol";
+/// Version of the EIP-712 signing domain of the voting protocol.
+pub const EIP712_DOMAIN_VERSION: &str = "1";
+
+/// Value returned by `isValidSignature` when an EIP-1271 contract accepts a signature.
+pub const ERC1271_MAGIC_VALUE: FixedBytes<4> = fixed_bytes!("1626ba7e");
+/// Suffix appended to the ERC-6492 signatures of counterfactual contract wallets.
+pub const ERC6492_DETECTION_SUFFIX: FixedBytes<32> =
+    fixed_bytes!("6492649264926492649264926492649264926492649264926492649264926492");
+
+sol! {
+    /// EIP-712 typed vote signed by the voter.
+    #[derive(Debug)]
+    struct Vote {
+        address voter;
+        address dao;
+        uint256 proposalId;
+        uint8 direction;
+        uint256 balance;
+        uint256 nonce;
+        uint64 deadline;
+    }
+}
+
+sol! {
+    /// EIP-1271 signature validation of smart contract wallets.
+    interface IERC1271 {
+        function isValidSignature(bytes32 hash, bytes memory signature) external view returns (bytes4 magicValue);
+    }
+
+    /// ERC-6492 universal signature validator, deploying counterfactual wallets before validating.
+    interface IUniversalSigValidator {
+        function isValidSig(address signer, bytes32 hash, bytes calldata signature) external returns (bool);
+    }
+}
+
+/// Returns the EIP-712 domain of the votes, bound to the chain and the plugin contract.
+pub fn vote_domain(chain_id: u64, plugin: Address) -> Eip712Domain {
+    eip712_domain! {
+        name: EIP712_DOMAIN_NAME,
+        version: EIP712_DOMAIN_VERSION,
+        chain_id: chain_id,
+        verifying_contract: plugin,
+    }
+}
+
+impl Vote {
+    pub fn new(
+        voter: Address,
+        dao: Address,
+        proposal_id: U256,
+        direction: u8,
+        balance: U256,
+        nonce: U256,
+        deadline: u64,
+    ) -> Self {
+        Self {
+            voter,
+            dao,
+            proposalId: proposal_id,
+            direction,
+            balance,
+            nonce,
+            deadline,
+        }
+    }
+
+    /// Computes the EIP-712 digest that has to be signed by the voter.
+    pub fn signing_hash(&self, chain_id: u64, plugin: Address) -> B256 {
+        self.eip712_signing_hash(&vote_domain(chain_id, plugin))
+    }
+}
+
+/// Signature of a counterfactual contract wallet, wrapped as specified by ERC-6492.
+pub struct Erc6492Signature {
+    pub factory: Address,
+    pub factory_calldata: Bytes,
+    pub signature: Bytes,
+}
+
+/// Returns whether the signature is wrapped as specified by ERC-6492.
+pub fn is_erc6492_signature(signature: &[u8]) -> bool {
+    signature.ends_with(ERC6492_DETECTION_SUFFIX.as_slice())
+}
+
+/// Decodes an ERC-6492 `abi.encode(factory, factoryCalldata, signature) ++ magicBytes` signature.
+pub fn decode_erc6492_signature(signature: &[u8]) -> Result<Erc6492Signature> {
+    let wrapped = signature
+        .strip_suffix(ERC6492_DETECTION_SUFFIX.as_slice())
+        .ok_or_else(|| anyhow!("signature is not wrapped as specified by ERC-6492"))?;
+    let (factory, factory_calldata, signature) =
+        <(Address, Bytes, Bytes)>::abi_decode_params(wrapped, true)?;
+    Ok(Erc6492Signature {
+        factory,
+        factory_calldata,
+        signature,
+    })
+}
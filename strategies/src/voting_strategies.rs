@@ -1,24 +1,131 @@
-use crate::Asset;
-use alloy_primitives::{Address, U256};
-use risc0_steel::EvmEnv;
+use crate::{
+    environment::StrategyEnv,
+    params::{no_params, StrategyParams},
+    strategy_id::StrategyId,
+    Asset, StrategyError,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
-pub trait VotingPowerStrategy {
+pub trait VotingPowerStrategy<E: StrategyEnv> {
+    /// Returns the voting power of `account`, given the `hint` provided by the host for it.
     fn process(
         &self,
-        env: &EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
+        env: &mut E,
         account: Address,
         asset: &Asset,
-    ) -> U256;
+        hint: &Bytes,
+    ) -> Result<U256, StrategyError>;
 
-    fn get_supply(
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError>;
+
+    /// Computes in the host the hint of an account, such as the token ids it holds, which
+    /// `process` verifies against the state. Strategies without hints keep this default.
+    fn hint(
         &self,
-        env: &EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
-        asset: &Asset,
-    ) -> U256;
+        _env: &mut E,
+        _account: Address,
+        _asset: &Asset,
+    ) -> Result<Bytes, StrategyError> {
+        Ok(Bytes::new())
+    }
+
+    /// Validates the `params` of the asset. Strategies without parameters keep this default.
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        no_params(params)
+    }
+}
+
+/// Voting power strategies registered by [`crate::Context::default`], as `(name, version)`.
+pub const REGISTERED: &[(&str, u32)] = &[
+    ("BalanceOf", 1),
+    ("GetPastVotes", 1),
+    ("ERC1155BalanceOf", 1),
+    ("ERC721OwnerOf", 1),
+    ("StakedBalanceOf", 1),
+    ("VotingEscrow", 1),
+    ("UniswapV2LP", 1),
+    ("UniswapV3Position", 1),
+    ("WrappedBalanceOf", 1),
+    ("VestingWallet", 1),
+    ("MerkleAllowlist", 1),
+    ("Quadratic", 1),
+    ("DynamicCall", 1),
+    ("StorageSlot", 1),
+];
+
+/// Voting power strategies registered in a [`crate::Context`], shared with the strategies
+/// applied on top of other registered ones, such as [`Quadratic`].
+pub type VotingPowerStrategies<E> =
+    Rc<RefCell<HashMap<StrategyId, Rc<dyn VotingPowerStrategy<E>>>>>;
+
+/// Builds the registered strategy of an identifier, see [`REGISTERED`]. The strategies applied on
+/// top of other ones look these up in `strategies`.
+pub fn registered<E: StrategyEnv + 'static>(
+    id: &StrategyId,
+    strategies: &VotingPowerStrategies<E>,
+) -> Option<Rc<dyn VotingPowerStrategy<E>>> {
+    let strategy: Rc<dyn VotingPowerStrategy<E>> = match (id.name(), id.version()) {
+        ("BalanceOf", 1) => Rc::new(BalanceOf),
+        ("GetPastVotes", 1) => Rc::new(GetPastVotes),
+        ("ERC1155BalanceOf", 1) => Rc::new(Erc1155BalanceOf),
+        ("ERC721OwnerOf", 1) => Rc::new(Erc721OwnerOf),
+        ("StakedBalanceOf", 1) => Rc::new(StakedBalanceOf),
+        ("VotingEscrow", 1) => Rc::new(VotingEscrow),
+        ("UniswapV2LP", 1) => Rc::new(UniswapV2Lp),
+        ("UniswapV3Position", 1) => Rc::new(UniswapV3Position),
+        ("WrappedBalanceOf", 1) => Rc::new(WrappedBalanceOf),
+        ("VestingWallet", 1) => Rc::new(VestingWallet),
+        ("MerkleAllowlist", 1) => Rc::new(MerkleAllowlist),
+        ("Quadratic", 1) => Rc::new(Quadratic::new(strategies)),
+        ("DynamicCall", 1) => Rc::new(DynamicCall),
+        ("StorageSlot", 1) => Rc::new(StorageSlot),
+        _ => return None,
+    };
+    Some(strategy)
+}
+
+/// Returns the strategy of an identifier among the registered `strategies`.
+pub(crate) fn resolve<E: StrategyEnv>(
+    strategies: &VotingPowerStrategies<E>,
+    id: &StrategyId,
+) -> Result<Rc<dyn VotingPowerStrategy<E>>, StrategyError> {
+    let strategies = strategies.borrow();
+    strategies
+        .get(id)
+        .cloned()
+        .ok_or_else(|| StrategyError::unknown_strategy(id, strategies.keys()))
 }
 
 mod balance_of;
+mod dynamic_call;
+mod erc1155_balance_of;
+mod erc721_owner_of;
 mod get_past_votes;
+mod merkle_allowlist;
+mod quadratic;
+mod shared;
+mod staked_balance_of;
+mod storage_slot;
+mod uniswap_v2_lp;
+mod uniswap_v3_position;
+mod vesting_wallet;
+mod voting_escrow;
+mod wrapped_balance_of;
 
 pub use balance_of::BalanceOf;
+pub use dynamic_call::DynamicCall;
+pub use erc1155_balance_of::Erc1155BalanceOf;
+pub use erc721_owner_of::Erc721OwnerOf;
 pub use get_past_votes::GetPastVotes;
+pub use merkle_allowlist::MerkleAllowlist;
+pub use quadratic::Quadratic;
+pub use staked_balance_of::StakedBalanceOf;
+pub use storage_slot::StorageSlot;
+pub use uniswap_v2_lp::UniswapV2Lp;
+pub use uniswap_v3_position::UniswapV3Position;
+pub use vesting_wallet::VestingWallet;
+pub use voting_escrow::VotingEscrow;
+pub use wrapped_balance_of::WrappedBalanceOf;
@@ -1,8 +1,7 @@
 use super::VotingPowerStrategy;
-use crate::Asset;
-use alloy_primitives::{Address, U256};
+use crate::{environment::StrategyEnv, Asset, StrategyError};
+use alloy_primitives::{Address, Bytes, U256};
 use alloy_sol_types::sol;
-use risc0_steel::{Contract, EvmEnv};
 
 sol! {
     /// ERC-20 balance function signature.
@@ -13,26 +12,21 @@ sol! {
 }
 
 pub struct BalanceOf;
-impl VotingPowerStrategy for BalanceOf {
+impl<E: StrategyEnv> VotingPowerStrategy<E> for BalanceOf {
     fn process(
         &self,
-        env: &EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
+        env: &mut E,
         account: Address,
         asset: &Asset,
-    ) -> U256 {
-        let asset_contract = Contract::new(asset.contract, env);
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
         let balance_call = IERC20::balanceOfCall { account };
-        let balance = asset_contract.call_builder(&balance_call).call();
-        U256::from(balance._0)
+        let balance = env.call(asset.contract, &balance_call)?;
+        Ok(U256::from(balance._0))
     }
-    fn get_supply(
-        &self,
-        env: &EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
-        asset: &Asset,
-    ) -> U256 {
-        let asset_contract = Contract::new(asset.contract, env);
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
         let total_supply_call = IERC20::getTotalSupplyCall {};
-        let total_supply = asset_contract.call_builder(&total_supply_call).call();
-        U256::from(total_supply._0)
+        let total_supply = env.call(asset.contract, &total_supply_call)?;
+        Ok(U256::from(total_supply._0))
     }
 }
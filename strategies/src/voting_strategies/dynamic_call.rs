@@ -0,0 +1,365 @@
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use risc0_steel::EvmBlockHeader;
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct DynamicCallParams {
+    /// Call returning the voting power of an account.
+    power: CallParams,
+    /// Call returning the total supply.
+    supply: CallParams,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct CallParams {
+    /// Contract called, the asset contract by default.
+    #[serde(default)]
+    contract: Option<Address>,
+    /// Signature of the function, such as `getPriorVotes(address,uint256)`.
+    signature: String,
+    /// Arguments of the function, in the order of the signature.
+    #[serde(default)]
+    args: Vec<Argument>,
+    /// Index of the returned `uint` among the return values, which must all be static up to it.
+    #[serde(default)]
+    return_index: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+enum Argument {
+    /// The account whose voting power is read, as an `address`.
+    Account,
+    /// Number of the snapshot block, as a `uint`.
+    BlockNumber,
+    /// Timestamp of the snapshot block, as a `uint`.
+    BlockTimestamp,
+    /// Value given in the config, as the JSON value of the type of the parameter.
+    Constant(serde_json::Value),
+}
+
+/// Static parameter types, encoded in a single word.
+#[derive(Clone, Copy)]
+enum ParamType {
+    Address,
+    Bool,
+    Uint(usize),
+    FixedBytes(usize),
+}
+
+impl FromStr for ParamType {
+    type Err = StrategyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let size = |digits: &str, max: usize, step: usize| match digits.parse::<usize>() {
+            Ok(size) if size > 0 && size <= max && size % step == 0 => Some(size),
+            _ => None,
+        };
+        let param_type = match s {
+            "address" => Some(ParamType::Address),
+            "bool" => Some(ParamType::Bool),
+            "uint" => Some(ParamType::Uint(256)),
+            _ => {
+                if let Some(bits) = s.strip_prefix("uint") {
+                    size(bits, 256, 8).map(ParamType::Uint)
+                } else if let Some(len) = s.strip_prefix("bytes") {
+                    size(len, 32, 1).map(ParamType::FixedBytes)
+                } else {
+                    None
+                }
+            }
+        };
+        param_type.ok_or_else(|| {
+            StrategyError::InvalidParams(format!("unsupported parameter type `{}`", s))
+        })
+    }
+}
+
+impl fmt::Display for ParamType {
+    /// Canonical name of the type, as hashed in function selectors.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamType::Address => write!(f, "address"),
+            ParamType::Bool => write!(f, "bool"),
+            ParamType::Uint(bits) => write!(f, "uint{}", bits),
+            ParamType::FixedBytes(len) => write!(f, "bytes{}", len),
+        }
+    }
+}
+
+/// Word of an argument, known once the account and the snapshot block are.
+enum ArgumentWord {
+    Account,
+    BlockNumber(usize),
+    BlockTimestamp(usize),
+    Constant(B256),
+}
+
+/// Call of the config, whose constant arguments are encoded.
+struct CallTemplate {
+    contract: Option<Address>,
+    selector: [u8; 4],
+    args: Vec<ArgumentWord>,
+    /// Offset of the returned word, whose end is known not to overflow.
+    return_offset: usize,
+}
+
+/// Encodes a constant argument as the word of its parameter type.
+fn encode_constant(
+    param_type: ParamType,
+    value: &serde_json::Value,
+) -> Result<B256, StrategyError> {
+    let invalid = || StrategyError::InvalidParams(format!("invalid constant argument {}", value));
+    match param_type {
+        ParamType::Address => {
+            let address: Address = serde_json::from_value(value.clone()).map_err(|_| invalid())?;
+            Ok(address.into_word())
+        }
+        ParamType::Bool => {
+            let value = value.as_bool().ok_or_else(invalid)?;
+            Ok(B256::from(U256::from(value as u8)))
+        }
+        ParamType::Uint(bits) => {
+            let value: U256 = serde_json::from_value(value.clone()).map_err(|_| invalid())?;
+            if value.bit_len() > bits {
+                return Err(invalid());
+            }
+            Ok(B256::from(value))
+        }
+        ParamType::FixedBytes(len) => {
+            let bytes: Bytes = serde_json::from_value(value.clone()).map_err(|_| invalid())?;
+            if bytes.len() != len {
+                return Err(invalid());
+            }
+            Ok(B256::right_padding_from(&bytes))
+        }
+    }
+}
+
+impl CallTemplate {
+    fn parse(params: CallParams) -> Result<Self, StrategyError> {
+        let invalid_signature = || {
+            StrategyError::InvalidParams(format!(
+                "invalid function signature `{}`",
+                params.signature
+            ))
+        };
+        let (name, types) = params
+            .signature
+            .strip_suffix(')')
+            .and_then(|signature| signature.split_once('('))
+            .ok_or_else(invalid_signature)?;
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(invalid_signature());
+        }
+        let param_types = if types.is_empty() {
+            Vec::new()
+        } else {
+            types
+                .split(',')
+                .map(ParamType::from_str)
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        if param_types.len() != params.args.len() {
+            return Err(StrategyError::InvalidParams(format!(
+                "`{}` takes {} arguments, {} are given",
+                params.signature,
+                param_types.len(),
+                params.args.len()
+            )));
+        }
+
+        let args = param_types
+            .iter()
+            .copied()
+            .zip(&params.args)
+            .map(|(param_type, arg)| match (arg, param_type) {
+                (Argument::Account, ParamType::Address) => Ok(ArgumentWord::Account),
+                (Argument::BlockNumber, ParamType::Uint(bits)) => {
+                    Ok(ArgumentWord::BlockNumber(bits))
+                }
+                (Argument::BlockTimestamp, ParamType::Uint(bits)) => {
+                    Ok(ArgumentWord::BlockTimestamp(bits))
+                }
+                (Argument::Constant(value), param_type) => {
+                    encode_constant(param_type, value).map(ArgumentWord::Constant)
+                }
+                _ => Err(StrategyError::InvalidParams(format!(
+                    "argument types do not match `{}`",
+                    params.signature
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The selector hashes the canonical types, `uint` being an alias of `uint256`.
+        let canonical_types: Vec<String> = param_types.iter().map(ToString::to_string).collect();
+        let canonical_signature = format!("{}({})", name, canonical_types.join(","));
+        let mut selector = [0; 4];
+        selector.copy_from_slice(&keccak256(canonical_signature.as_bytes())[..4]);
+        let return_offset = params
+            .return_index
+            .checked_mul(32)
+            .filter(|offset| offset.checked_add(32).is_some())
+            .ok_or_else(|| {
+                StrategyError::InvalidParams(format!(
+                    "return index {} is out of range",
+                    params.return_index
+                ))
+            })?;
+        Ok(Self {
+            contract: params.contract,
+            selector,
+            args,
+            return_offset,
+        })
+    }
+
+    /// Calls the function for the account at the snapshot block, and decodes the returned `uint`.
+    fn call<E: StrategyEnv>(
+        &self,
+        env: &mut E,
+        asset: &Asset,
+        account: Address,
+    ) -> Result<U256, StrategyError> {
+        let block_number = env.block_commitment().blockNumber;
+        let timestamp = U256::from(env.header().timestamp());
+        let uint_word = |value: U256, bits: usize| {
+            if value.bit_len() > bits {
+                return Err(StrategyError::ArithmeticOverflow);
+            }
+            Ok(B256::from(value))
+        };
+        let mut calldata = self.selector.to_vec();
+        for arg in &self.args {
+            let word = match arg {
+                ArgumentWord::Account => account.into_word(),
+                ArgumentWord::BlockNumber(bits) => uint_word(block_number, *bits)?,
+                ArgumentWord::BlockTimestamp(bits) => uint_word(timestamp, *bits)?,
+                ArgumentWord::Constant(word) => *word,
+            };
+            calldata.extend_from_slice(word.as_slice());
+        }
+
+        let contract = self.contract.unwrap_or(asset.contract);
+        let returns = env.call_raw(contract, calldata.into())?;
+        returns
+            .get(self.return_offset..self.return_offset + 32)
+            .map(U256::from_be_slice)
+            .ok_or_else(|| {
+                StrategyError::InvalidParams(format!(
+                    "call to {} returned no value at index {}",
+                    contract,
+                    self.return_offset / 32
+                ))
+            })
+    }
+}
+
+impl DynamicCallParams {
+    fn parse(params: &StrategyParams) -> Result<(CallTemplate, CallTemplate), StrategyError> {
+        let params: Self = parse_params(params)?;
+        let power = CallTemplate::parse(params.power)?;
+        let supply = CallTemplate::parse(params.supply)?;
+        if supply
+            .args
+            .iter()
+            .any(|arg| matches!(arg, ArgumentWord::Account))
+        {
+            return Err(StrategyError::InvalidParams(
+                "the supply call cannot take the account".to_string(),
+            ));
+        }
+        Ok((power, supply))
+    }
+}
+
+/// Voting power read by functions given in the config, for tokens without a strategy of their
+/// own, such as Compound's `getPriorVotes` or snapshot tokens' `balanceOfAt`.
+///
+/// The calls are encoded from the signature and the template of the arguments, which can be the
+/// account, the number or the timestamp of the snapshot block, and constants. Only parameters
+/// and return values of static types, `address`, `bool`, `uint<N>` and `bytes<N>`, are
+/// supported.
+pub struct DynamicCall;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for DynamicCall {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let (power, _) = DynamicCallParams::parse(&asset.params)?;
+        power.call(env, asset, account)
+    }
+
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let (_, supply) = DynamicCallParams::parse(&asset.params)?;
+        supply.call(env, asset, Address::ZERO)
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        DynamicCallParams::parse(params).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(signature: &str, args: Vec<Argument>, return_index: usize) -> CallTemplate {
+        CallTemplate::parse(CallParams {
+            contract: None,
+            signature: signature.to_string(),
+            args,
+            return_index,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn selector_hashes_canonical_types() {
+        // `getPriorVotes(address,uint256)` of Compound's `Comp`.
+        let selector = [0x78, 0x2d, 0x6f, 0xe1];
+        let args = || vec![Argument::Account, Argument::BlockNumber];
+        assert_eq!(
+            template("getPriorVotes(address,uint256)", args(), 0).selector,
+            selector
+        );
+        assert_eq!(
+            template("getPriorVotes(address,uint)", args(), 0).selector,
+            selector
+        );
+    }
+
+    #[test]
+    fn return_index_out_of_range_is_rejected() {
+        let parse = |return_index| {
+            CallTemplate::parse(CallParams {
+                contract: None,
+                signature: "totalSupply()".to_string(),
+                args: Vec::new(),
+                return_index,
+            })
+        };
+        assert!(matches!(
+            parse(usize::MAX),
+            Err(StrategyError::InvalidParams(_))
+        ));
+        assert!(matches!(
+            parse(usize::MAX / 32),
+            Err(StrategyError::InvalidParams(_))
+        ));
+        assert_eq!(parse(2).unwrap().return_offset, 64);
+    }
+}
@@ -0,0 +1,104 @@
+use super::shared::{default_weight, SupplySource};
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::sol;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+sol! {
+    /// ERC-1155 balance function signature, and total supply of the `ERC1155Supply` extension.
+    interface IERC1155 {
+        function balanceOf(address account, uint256 id) external view returns (uint256);
+        function totalSupply(uint256 id) external view returns (uint256);
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct Erc1155Params {
+    /// Token ids granting voting power.
+    ids: Vec<WeightedId>,
+    /// Weighted sum of the supplies of the ids, read from the `ERC1155Supply` extension, or a
+    /// supply declared in the config for tokens without supply tracking, which ERC-1155 does not
+    /// standardize.
+    supply: SupplySource,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WeightedId {
+    id: U256,
+    /// Voting power granted by each token of the id.
+    #[serde(default = "default_weight")]
+    weight: U256,
+}
+
+impl Erc1155Params {
+    fn parse(params: &StrategyParams) -> Result<Self, StrategyError> {
+        let params: Self = parse_params(params)?;
+        if params.ids.is_empty() {
+            return Err(StrategyError::InvalidParams(
+                "at least one token id is required".to_string(),
+            ));
+        }
+        let mut ids = HashSet::new();
+        if let Some(duplicate) = params.ids.iter().find(|id| !ids.insert(id.id)) {
+            return Err(StrategyError::InvalidParams(format!(
+                "token id {} is listed more than once",
+                duplicate.id
+            )));
+        }
+        Ok(params)
+    }
+}
+
+/// Weighted sum of the amounts returned for each id.
+fn weighted_sum(
+    ids: &[WeightedId],
+    mut amount_of: impl FnMut(U256) -> Result<U256, StrategyError>,
+) -> Result<U256, StrategyError> {
+    ids.iter().try_fold(U256::from(0), |acc, weighted_id| {
+        amount_of(weighted_id.id)?
+            .checked_mul(weighted_id.weight)
+            .and_then(|amount| acc.checked_add(amount))
+            .ok_or(StrategyError::ArithmeticOverflow)
+    })
+}
+
+/// Voting power of the holders of some ids of an ERC-1155 token, weighted per id.
+pub struct Erc1155BalanceOf;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for Erc1155BalanceOf {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params = Erc1155Params::parse(&asset.params)?;
+        weighted_sum(&params.ids, |id| {
+            let balance_call = IERC1155::balanceOfCall { account, id };
+            Ok(env.call(asset.contract, &balance_call)?._0)
+        })
+    }
+
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params = Erc1155Params::parse(&asset.params)?;
+        match params.supply {
+            SupplySource::TotalSupply => weighted_sum(&params.ids, |id| {
+                let total_supply_call = IERC1155::totalSupplyCall { id };
+                Ok(env.call(asset.contract, &total_supply_call)?._0)
+            }),
+            SupplySource::Fixed { amount } => Ok(amount),
+        }
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        Erc1155Params::parse(params).map(|_| ())
+    }
+}
@@ -0,0 +1,207 @@
+use super::shared::{default_weight, SupplySource};
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::{sol, SolValue};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+sol! {
+    /// ERC-721 function signatures, and enumeration of the `ERC721Enumerable` extension.
+    interface IERC721 {
+        function ownerOf(uint256 tokenId) external view returns (address);
+        function balanceOf(address owner) external view returns (uint256);
+        function totalSupply() external view returns (uint256);
+        function tokenOfOwnerByIndex(address owner, uint256 index) external view returns (uint256);
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct Erc721Params {
+    /// Weights of some token ids or ranges of ids, which must not overlap.
+    #[serde(default)]
+    weights: Vec<WeightRule>,
+    /// Weight of the tokens matched by none of the rules.
+    #[serde(default = "default_weight")]
+    default_weight: U256,
+    /// `totalSupply()` of the `ERC721Enumerable` extension, for collections whose tokens all
+    /// have the default weight, or a weighted supply declared in the config.
+    supply: SupplySource,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WeightRule {
+    Id {
+        id: U256,
+        weight: U256,
+    },
+    /// Ids from `from` to `to`, both included.
+    Range {
+        from: U256,
+        to: U256,
+        weight: U256,
+    },
+}
+
+impl WeightRule {
+    fn bounds(&self) -> (U256, U256) {
+        match self {
+            WeightRule::Id { id, .. } => (*id, *id),
+            WeightRule::Range { from, to, .. } => (*from, *to),
+        }
+    }
+
+    fn weight(&self) -> U256 {
+        match self {
+            WeightRule::Id { weight, .. } | WeightRule::Range { weight, .. } => *weight,
+        }
+    }
+}
+
+impl Erc721Params {
+    fn parse(params: &StrategyParams) -> Result<Self, StrategyError> {
+        let params: Self = parse_params(params)?;
+        let mut bounds: Vec<(U256, U256)> = params.weights.iter().map(WeightRule::bounds).collect();
+        bounds.sort();
+        if let Some((from, to)) = bounds.iter().find(|(from, to)| from > to) {
+            return Err(StrategyError::InvalidParams(format!(
+                "range from {} to {} is empty",
+                from, to
+            )));
+        }
+        if let Some(overlap) = bounds.windows(2).find(|pair| pair[1].0 <= pair[0].1) {
+            return Err(StrategyError::InvalidParams(format!(
+                "weights of token ids {} and {} overlap",
+                overlap[0].0, overlap[1].0
+            )));
+        }
+        if matches!(params.supply, SupplySource::TotalSupply) && !params.weights.is_empty() {
+            return Err(StrategyError::InvalidParams(
+                "the supply of a collection with weights must be fixed".to_string(),
+            ));
+        }
+        Ok(params)
+    }
+
+    fn weight_of(&self, id: U256) -> U256 {
+        self.weights
+            .iter()
+            .find(|rule| {
+                let (from, to) = rule.bounds();
+                from <= id && id <= to
+            })
+            .map_or(self.default_weight, WeightRule::weight)
+    }
+}
+
+/// Decodes a hint listing token ids, as the ABI encoded `uint256[]` of the ids. Listing an id
+/// more than once is rejected, so that its voting power cannot be counted twice.
+pub(super) fn decode_token_ids(hint: &Bytes) -> Result<Vec<U256>, StrategyError> {
+    if hint.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids = Vec::<U256>::abi_decode(hint, true)
+        .map_err(|err| StrategyError::InvalidHint(err.to_string()))?;
+    let mut listed = HashSet::new();
+    if let Some(duplicate) = ids.iter().find(|id| !listed.insert(**id)) {
+        return Err(StrategyError::InvalidHint(format!(
+            "token id {} is listed more than once",
+            duplicate
+        )));
+    }
+    Ok(ids)
+}
+
+/// Checks with `ownerOf` that a token of a collection is owned by the account.
+pub(super) fn check_owner<E: StrategyEnv>(
+    env: &mut E,
+    collection: Address,
+    id: U256,
+    account: Address,
+) -> Result<(), StrategyError> {
+    let owner_call = IERC721::ownerOfCall { tokenId: id };
+    let owner = env.call(collection, &owner_call)?._0;
+    if owner != account {
+        return Err(StrategyError::InvalidHint(format!(
+            "token id {} is not owned by {}",
+            id, account
+        )));
+    }
+    Ok(())
+}
+
+/// Lists the tokens of a collection owned by the account, through the `ERC721Enumerable`
+/// extension.
+pub(super) fn tokens_of_owner<E: StrategyEnv>(
+    env: &mut E,
+    collection: Address,
+    account: Address,
+) -> Result<Vec<U256>, StrategyError> {
+    let balance_call = IERC721::balanceOfCall { owner: account };
+    let balance = env.call(collection, &balance_call)?._0;
+    let balance = u64::try_from(balance).map_err(|_| StrategyError::ArithmeticOverflow)?;
+    (0..balance)
+        .map(|index| {
+            let token_call = IERC721::tokenOfOwnerByIndexCall {
+                owner: account,
+                index: U256::from(index),
+            };
+            Ok(env.call(collection, &token_call)?._0)
+        })
+        .collect()
+}
+
+/// Voting power of the owners of the tokens of an ERC-721 collection, weighted per token id.
+///
+/// The hint of an account is the ABI encoded `uint256[]` of the ids it owns, whose ownership is
+/// checked with `ownerOf` at the snapshot block.
+pub struct Erc721OwnerOf;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for Erc721OwnerOf {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params = Erc721Params::parse(&asset.params)?;
+        let ids = decode_token_ids(hint)?;
+        ids.iter().try_fold(U256::from(0), |acc, id| {
+            check_owner(env, asset.contract, *id, account)?;
+            acc.checked_add(params.weight_of(*id))
+                .ok_or(StrategyError::ArithmeticOverflow)
+        })
+    }
+
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params = Erc721Params::parse(&asset.params)?;
+        match params.supply {
+            SupplySource::TotalSupply => {
+                let total_supply_call = IERC721::totalSupplyCall {};
+                let total_supply = env.call(asset.contract, &total_supply_call)?._0;
+                total_supply
+                    .checked_mul(params.default_weight)
+                    .ok_or(StrategyError::ArithmeticOverflow)
+            }
+            SupplySource::Fixed { amount } => Ok(amount),
+        }
+    }
+
+    /// Lists the ids owned by the account through the `ERC721Enumerable` extension. The hints of
+    /// collections without it must be given to the host.
+    fn hint(&self, env: &mut E, account: Address, asset: &Asset) -> Result<Bytes, StrategyError> {
+        Ok(tokens_of_owner(env, asset.contract, account)?
+            .abi_encode()
+            .into())
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        Erc721Params::parse(params).map(|_| ())
+    }
+}
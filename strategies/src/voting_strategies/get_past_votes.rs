@@ -1,8 +1,7 @@
 use super::VotingPowerStrategy;
-use crate::Asset;
-use alloy_primitives::{Address, U256};
+use crate::{environment::StrategyEnv, Asset, StrategyError};
+use alloy_primitives::{Address, Bytes, U256};
 use alloy_sol_types::sol;
-use risc0_steel::{Contract, EvmEnv};
 
 sol! {
     /// ERC-20 balance function signature.
@@ -13,34 +12,29 @@ sol! {
 }
 
 pub struct GetPastVotes;
-impl VotingPowerStrategy for GetPastVotes {
+impl<E: StrategyEnv> VotingPowerStrategy<E> for GetPastVotes {
     fn process(
         &self,
-        env: &EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
+        env: &mut E,
         account: Address,
         asset: &Asset,
-    ) -> U256 {
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
         let block_number = env.block_commitment().blockNumber;
-        let asset_contract = Contract::new(asset.contract, env);
         let balance_call = IERC20Votes::getPastVotesCall {
             account,
             blockNumber: block_number,
         };
-        let balance = asset_contract.call_builder(&balance_call).call();
-        U256::from(balance._0)
+        let balance = env.call(asset.contract, &balance_call)?;
+        Ok(U256::from(balance._0))
     }
 
-    fn get_supply(
-        &self,
-        env: &EvmEnv<risc0_steel::StateDb, risc0_steel::ethereum::EthBlockHeader>,
-        asset: &Asset,
-    ) -> U256 {
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
         let block_number = env.block_commitment().blockNumber;
-        let asset_contract = Contract::new(asset.contract, env);
         let supply_call = IERC20Votes::getPastTotalSupplyCall {
             timepoint: block_number,
         };
-        let supply = asset_contract.call_builder(&supply_call).call();
-        U256::from(supply._0)
+        let supply = env.call(asset.contract, &supply_call)?;
+        Ok(U256::from(supply._0))
     }
 }
@@ -0,0 +1,137 @@
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy_sol_types::SolValue;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct MerkleAllowlistParams {
+    /// Root of the tree of the `(address, uint256)` weights of the allowlist.
+    root: B256,
+    /// Sum of the weights of the allowlist.
+    total_weight: U256,
+}
+
+/// Leaf of the weight of an account, hashed twice as in OpenZeppelin's `StandardMerkleTree`.
+fn leaf(account: Address, weight: U256) -> B256 {
+    keccak256(keccak256((account, weight).abi_encode()))
+}
+
+/// Root of the tree of a leaf and its proof, hashing the sorted pairs of nodes as OpenZeppelin's
+/// `MerkleProof`.
+fn process_proof(leaf: B256, proof: &[B256]) -> B256 {
+    proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak256([node.as_slice(), sibling.as_slice()].concat())
+        } else {
+            keccak256([sibling.as_slice(), node.as_slice()].concat())
+        }
+    })
+}
+
+/// Voting power of the accounts of an allowlist committed by the Merkle root of their weights in
+/// the config, such as the members of a grants council. No contract is read, so the asset
+/// contract is not used.
+///
+/// The hint of an account is the `abi.encode(weight, proof)` of its leaf, which the host cannot
+/// compute and must be given. Accounts without hint have no voting power.
+pub struct MerkleAllowlist;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for MerkleAllowlist {
+    fn process(
+        &self,
+        _env: &mut E,
+        account: Address,
+        asset: &Asset,
+        hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params: MerkleAllowlistParams = parse_params(&asset.params)?;
+        if hint.is_empty() {
+            return Ok(U256::from(0));
+        }
+        let (weight, proof) = <(U256, Vec<B256>)>::abi_decode_params(hint, true)
+            .map_err(|err| StrategyError::InvalidHint(err.to_string()))?;
+        if process_proof(leaf(account, weight), &proof) != params.root {
+            return Err(StrategyError::InvalidHint(format!(
+                "invalid Merkle proof of the weight of {}",
+                account
+            )));
+        }
+        Ok(weight)
+    }
+
+    fn get_supply(&self, _env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params: MerkleAllowlistParams = parse_params(&asset.params)?;
+        Ok(params.total_weight)
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        parse_params::<MerkleAllowlistParams>(params).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::MockEnv;
+    use alloy_primitives::{address, b256};
+    use serde_json::json;
+
+    /// Tree of the `@openzeppelin/merkle-tree` README, built by `StandardMerkleTree.of(values,
+    /// ["address", "uint256"])`.
+    const ROOT: B256 = b256!("d4dee0beab2d53f2cc83e567171bd2820e49898130a22622b10ead383e90bd77");
+    const ACCOUNT: Address = address!("1111111111111111111111111111111111111111");
+    const PROOF: B256 = b256!("b92c48e9d7abe27fd8dfd6b5dfdbfb1c9a463f80c712b66f3a5180a090cccafc");
+
+    fn weight() -> U256 {
+        U256::from(5_000_000_000_000_000_000u128)
+    }
+
+    fn allowlist_asset() -> Asset {
+        serde_json::from_value(json!({
+            "contract": "0x0000000000000000000000000000000000000000",
+            "chainId": 1,
+            "votingPowerStrategy": "MerkleAllowlist",
+            "params": { "root": ROOT, "totalWeight": "7500000000000000000" },
+            "delegation": {
+                "contract": "0x32Bb2dB7826cf342743fe80832Fe4DF725879C2D",
+                "strategy": "SplitDelegation",
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn proves_the_leaf_of_an_openzeppelin_tree() {
+        let other_leaf = leaf(
+            address!("2222222222222222222222222222222222222222"),
+            U256::from(2_500_000_000_000_000_000u128),
+        );
+        assert_eq!(other_leaf, PROOF);
+        assert_eq!(process_proof(leaf(ACCOUNT, weight()), &[PROOF]), ROOT);
+    }
+
+    #[test]
+    fn accepts_the_weight_proven_by_the_hint() {
+        let hint = (weight(), vec![PROOF]).abi_encode_params().into();
+        let power = MerkleAllowlist
+            .process(&mut MockEnv::default(), ACCOUNT, &allowlist_asset(), &hint)
+            .unwrap();
+        assert_eq!(power, weight());
+    }
+
+    #[test]
+    fn rejects_a_weight_not_in_the_tree() {
+        let hint = (weight() + U256::from(1), vec![PROOF])
+            .abi_encode_params()
+            .into();
+        assert!(matches!(
+            MerkleAllowlist.process(&mut MockEnv::default(), ACCOUNT, &allowlist_asset(), &hint),
+            Err(StrategyError::InvalidHint(_))
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+use super::shared::SupplySource;
+use super::{resolve, VotingPowerStrategies, VotingPowerStrategy};
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    strategy_id::StrategyId,
+    Asset, StrategyError,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct QuadraticParams {
+    /// Strategy whose voting powers are square rooted, among the ones registered in the context.
+    strategy: StrategyId,
+    /// Parameters of the inner strategy.
+    #[serde(default)]
+    params: StrategyParams,
+    /// Upper bound of the sum of the square rooted voting powers, declared in the config. The
+    /// square root of the supply of the inner strategy does not bound that sum, and the number
+    /// of holders sharing the supply cannot be proven, so the supply must be fixed.
+    supply: SupplySource,
+}
+
+impl QuadraticParams {
+    fn parse(params: &StrategyParams) -> Result<Self, StrategyError> {
+        let params: Self = parse_params(params)?;
+        if params.strategy.name() == "Quadratic" {
+            return Err(StrategyError::InvalidParams(
+                "the inner strategy must not be quadratic".to_string(),
+            ));
+        }
+        if matches!(params.supply, SupplySource::TotalSupply) {
+            return Err(StrategyError::InvalidParams(
+                "the supply of quadratic voting powers must be fixed".to_string(),
+            ));
+        }
+        Ok(params)
+    }
+
+    fn inner<E: StrategyEnv>(
+        &self,
+        strategies: &Weak<InnerStrategies<E>>,
+    ) -> Result<Rc<dyn VotingPowerStrategy<E>>, StrategyError> {
+        // The strategies outlive the context, which owns the quadratic strategy.
+        let strategies = strategies
+            .upgrade()
+            .expect("strategies of the context are dropped");
+        resolve(&strategies, &self.strategy)
+    }
+
+    /// The asset as configured for the inner strategy.
+    fn inner_asset(&self, asset: &Asset) -> Asset {
+        Asset {
+            voting_power_strategy: self.strategy.clone(),
+            params: self.params.clone(),
+            ..asset.clone()
+        }
+    }
+}
+
+/// Integer square root, rounded down, by Newton's method from a power of two above the root.
+fn sqrt(value: U256) -> U256 {
+    if value < U256::from(2) {
+        return value;
+    }
+    let mut root = U256::from(1) << value.bit_len().div_ceil(2);
+    loop {
+        let next = (root + value / root) >> 1;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+type InnerStrategies<E> = RefCell<HashMap<StrategyId, Rc<dyn VotingPowerStrategy<E>>>>;
+
+/// Quadratic voting power, as the integer square root of the voting power of an account given by
+/// an inner strategy, configured with its parameters.
+pub struct Quadratic<E: StrategyEnv> {
+    /// Strategies of the context the inner strategy is resolved in. The reference is weak, as
+    /// the quadratic strategy is itself one of them.
+    strategies: Weak<InnerStrategies<E>>,
+}
+
+impl<E: StrategyEnv> Quadratic<E> {
+    pub fn new(strategies: &VotingPowerStrategies<E>) -> Self {
+        Self {
+            strategies: Rc::downgrade(strategies),
+        }
+    }
+}
+
+impl<E: StrategyEnv> VotingPowerStrategy<E> for Quadratic<E> {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params = QuadraticParams::parse(&asset.params)?;
+        let inner_asset = params.inner_asset(asset);
+        let power = params
+            .inner(&self.strategies)?
+            .process(env, account, &inner_asset, hint)?;
+        Ok(sqrt(power))
+    }
+
+    fn get_supply(&self, _env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params = QuadraticParams::parse(&asset.params)?;
+        match params.supply {
+            SupplySource::Fixed { amount } => Ok(amount),
+            SupplySource::TotalSupply => unreachable!("rejected by `QuadraticParams::parse`"),
+        }
+    }
+
+    /// The hint of the inner strategy.
+    fn hint(&self, env: &mut E, account: Address, asset: &Asset) -> Result<Bytes, StrategyError> {
+        let params = QuadraticParams::parse(&asset.params)?;
+        let inner_asset = params.inner_asset(asset);
+        params
+            .inner(&self.strategies)?
+            .hint(env, account, &inner_asset)
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        let params = QuadraticParams::parse(params)?;
+        params
+            .inner(&self.strategies)?
+            .validate_params(&params.params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{environment::MockEnv, Context};
+    use serde_json::json;
+
+    /// Checks that `root` is the integer square root of `value`, `root² <= value < (root + 1)²`.
+    fn assert_sqrt(value: U256) {
+        let root = sqrt(value);
+        let square = root.checked_mul(root).expect("root² overflows");
+        assert!(square <= value, "sqrt({}) = {} is too large", value, root);
+        // `(root + 1)²` only overflows when it is above any value.
+        if let Some(next_square) = (root + U256::from(1)).checked_pow(U256::from(2)) {
+            assert!(
+                value < next_square,
+                "sqrt({}) = {} is too small",
+                value,
+                root
+            );
+        }
+    }
+
+    #[test]
+    fn sqrt_of_edge_values() {
+        assert_eq!(sqrt(U256::ZERO), U256::ZERO);
+        assert_eq!(sqrt(U256::from(1)), U256::from(1));
+        assert_eq!(sqrt(U256::MAX), U256::from(u128::MAX));
+        for value in [
+            U256::ZERO,
+            U256::from(1),
+            U256::from(2),
+            U256::from(3),
+            U256::MAX,
+        ] {
+            assert_sqrt(value);
+        }
+    }
+
+    #[test]
+    fn sqrt_around_perfect_squares() {
+        let roots = (1..=64u64)
+            .map(U256::from)
+            .chain((1..=128).map(|bits| U256::MAX >> (256 - bits)))
+            .chain((0..128).map(|bit| U256::from(1) << bit));
+        for root in roots {
+            let square = root * root;
+            assert_eq!(sqrt(square), root);
+            assert_eq!(sqrt(square - U256::from(1)), root - U256::from(1));
+            if let Some(above) = square.checked_add(U256::from(1)) {
+                assert_eq!(sqrt(above), root);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_of_random_values() {
+        // xorshift64*, so that the values are reproducible without a dependency.
+        let mut state = 0x9e3779b97f4a7c15u64;
+        let mut next = || {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            state.wrapping_mul(0x2545f4914f6cdd1d)
+        };
+        for _ in 0..10_000 {
+            let value = U256::from_limbs([next(), next(), next(), next()]);
+            // Values of every bit length, not only the ones close to 2^256.
+            assert_sqrt(value >> (next() % 256));
+        }
+    }
+
+    /// Strategy giving the same voting power to every account, registered by the tests only.
+    struct Constant;
+    impl VotingPowerStrategy<MockEnv> for Constant {
+        fn process(
+            &self,
+            _env: &mut MockEnv,
+            _account: Address,
+            _asset: &Asset,
+            _hint: &Bytes,
+        ) -> Result<U256, StrategyError> {
+            Ok(U256::from(144))
+        }
+
+        fn get_supply(&self, _env: &mut MockEnv, _asset: &Asset) -> Result<U256, StrategyError> {
+            Ok(U256::from(144))
+        }
+    }
+
+    fn quadratic_asset(inner: &str) -> Asset {
+        serde_json::from_value(json!({
+            "contract": "0x185Bb1cca668C474214e934028A3e4BB7A5E6525",
+            "chainId": 1,
+            "votingPowerStrategy": "Quadratic",
+            "params": {
+                "strategy": inner,
+                "supply": { "source": "fixed", "amount": "1000" },
+            },
+            "delegation": {
+                "contract": "0x32Bb2dB7826cf342743fe80832Fe4DF725879C2D",
+                "strategy": "SplitDelegation",
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn resolves_the_inner_strategy_in_the_context() {
+        let mut context = Context::default(1, MockEnv::default());
+        let asset = quadratic_asset("Constant");
+        let quadratic = StrategyId::new("Quadratic", 1);
+        assert!(context
+            .process_voting_strategy(&quadratic, Address::ZERO, &asset, &Bytes::new())
+            .is_err());
+
+        // Strategies added to the context can be used as inner strategies.
+        context.add_strategy(StrategyId::new("Constant", 1), Box::new(Constant));
+        assert_eq!(
+            context
+                .process_voting_strategy(&quadratic, Address::ZERO, &asset, &Bytes::new())
+                .unwrap(),
+            U256::from(12)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_inner_strategies_listing_the_registered_ones() {
+        let mut context = Context::default(1, MockEnv::default());
+        let err = context
+            .process_voting_strategy(
+                &StrategyId::new("Quadratic", 1),
+                Address::ZERO,
+                &quadratic_asset("Constant"),
+                &Bytes::new(),
+            )
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("unknown strategy `Constant@1`, expected one of: BalanceOf@1, "));
+    }
+}
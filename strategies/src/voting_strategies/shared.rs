@@ -0,0 +1,47 @@
+//! Declarations shared by the voting power strategies.
+
+use crate::{environment::StrategyEnv, StrategyError};
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::sol;
+use serde::Deserialize;
+
+sol! {
+    /// ERC-20 function signatures, of a token or of a receipt of its deposit.
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+        function totalSupply() external view returns (uint256);
+    }
+}
+
+/// Balance of an ERC-20 token at the snapshot block.
+pub(crate) fn balance_of<E: StrategyEnv>(
+    env: &mut E,
+    token: Address,
+    account: Address,
+) -> Result<U256, StrategyError> {
+    Ok(env.call(token, &IERC20::balanceOfCall { account })?._0)
+}
+
+/// Total supply of an ERC-20 token at the snapshot block.
+pub(crate) fn total_supply<E: StrategyEnv>(
+    env: &mut E,
+    token: Address,
+) -> Result<U256, StrategyError> {
+    Ok(env.call(token, &IERC20::totalSupplyCall {})?._0)
+}
+
+/// Weight of a token, or of an asset, when the config does not set one.
+pub(crate) fn default_weight() -> U256 {
+    U256::from(1)
+}
+
+/// Source of the total supply of a strategy whose token does not expose a supply matching its
+/// voting powers, such as a supply weighted by the config.
+#[derive(Deserialize)]
+#[serde(tag = "source", rename_all = "camelCase")]
+pub(crate) enum SupplySource {
+    /// Supply read from the token, as documented by the strategy.
+    TotalSupply,
+    /// Supply declared in the config.
+    Fixed { amount: U256 },
+}
@@ -0,0 +1,84 @@
+use super::shared::{balance_of, total_supply};
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::sol;
+use serde::Deserialize;
+
+sol! {
+    /// Staking contract keeping the staked balances without receipt token.
+    interface IStaking {
+        function stakedBalance(address account) external view returns (uint256);
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct StakingParams {
+    staking_contract: Address,
+    interface: StakingInterface,
+    /// Whether the tokens held outside of the staking contract count as well.
+    #[serde(default)]
+    include_liquid: bool,
+}
+
+/// Interface through which the staking contract exposes the staked balances.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum StakingInterface {
+    /// The staking contract is the ERC-20 receipt token of the stake.
+    BalanceOf,
+    /// The staking contract implements `stakedBalance(address)`.
+    StakedBalance,
+}
+
+/// Voting power of the tokens of an account in a staking contract, optionally added to the
+/// tokens it holds. The asset contract is the staked token.
+pub struct StakedBalanceOf;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for StakedBalanceOf {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params: StakingParams = parse_params(&asset.params)?;
+        let staked = match params.interface {
+            StakingInterface::BalanceOf => balance_of(env, params.staking_contract, account)?,
+            StakingInterface::StakedBalance => {
+                let staked_call = IStaking::stakedBalanceCall { account };
+                env.call(params.staking_contract, &staked_call)?._0
+            }
+        };
+        if !params.include_liquid {
+            return Ok(staked);
+        }
+        let liquid = balance_of(env, asset.contract, account)?;
+        staked
+            .checked_add(liquid)
+            .ok_or(StrategyError::ArithmeticOverflow)
+    }
+
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params: StakingParams = parse_params(&asset.params)?;
+        if params.include_liquid {
+            // The staked tokens are part of the supply of the token.
+            return total_supply(env, asset.contract);
+        }
+        match params.interface {
+            StakingInterface::BalanceOf => total_supply(env, params.staking_contract),
+            StakingInterface::StakedBalance => {
+                balance_of(env, asset.contract, params.staking_contract)
+            }
+        }
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        parse_params::<StakingParams>(params).map(|_| ())
+    }
+}
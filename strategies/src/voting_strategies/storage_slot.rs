@@ -0,0 +1,243 @@
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{keccak256, Address, Bytes, U256};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct StorageSlotParams {
+    /// Slot of the balance of an account.
+    balance: SlotParams,
+    /// Slot of the total supply.
+    supply: SlotParams,
+    #[serde(default)]
+    layout: Layout,
+}
+
+/// Hashing of the slots of mapping values, which differs between compilers.
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+enum Layout {
+    /// `keccak256(key . slot)`.
+    #[default]
+    Solidity,
+    /// `keccak256(slot . key)`.
+    Vyper,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct SlotParams {
+    /// Slot of the variable, or of the outermost mapping.
+    slot: U256,
+    /// Keys of the nested mappings, from the outermost one.
+    #[serde(default)]
+    keys: Vec<MappingKey>,
+    /// Offset of the field in the mapped struct.
+    #[serde(default)]
+    offset: U256,
+    /// Offset, in bits from the least significant one, of a value packed in the slot.
+    #[serde(default)]
+    shift: usize,
+    /// Size, in bits, of a value packed in the slot.
+    #[serde(default = "default_bits")]
+    bits: usize,
+}
+
+fn default_bits() -> usize {
+    256
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+enum MappingKey {
+    /// The account whose balance is read.
+    Account,
+    /// Key given in the config, as the value of its word.
+    Constant(U256),
+}
+
+impl StorageSlotParams {
+    fn parse(params: &StrategyParams) -> Result<Self, StrategyError> {
+        let params: Self = parse_params(params)?;
+        for slot in [&params.balance, &params.supply] {
+            let end = slot.shift.checked_add(slot.bits);
+            if slot.bits == 0 || !matches!(end, Some(end) if end <= 256) {
+                return Err(StrategyError::InvalidParams(format!(
+                    "{} bits from bit {} do not fit in a slot",
+                    slot.bits, slot.shift
+                )));
+            }
+        }
+        if params
+            .supply
+            .keys
+            .iter()
+            .any(|key| matches!(key, MappingKey::Account))
+        {
+            return Err(StrategyError::InvalidParams(
+                "the supply slot cannot be keyed by the account".to_string(),
+            ));
+        }
+        Ok(params)
+    }
+}
+
+impl SlotParams {
+    /// Slot of the value for the account, hashing the keys of the nested mappings.
+    fn slot_of(&self, layout: Layout, account: Address) -> U256 {
+        let slot = self.keys.iter().fold(self.slot, |slot, key| {
+            let key = match key {
+                MappingKey::Account => account.into_word().0,
+                MappingKey::Constant(key) => key.to_be_bytes::<32>(),
+            };
+            let slot = slot.to_be_bytes::<32>();
+            let preimage = match layout {
+                Layout::Solidity => [key, slot].concat(),
+                Layout::Vyper => [slot, key].concat(),
+            };
+            U256::from_be_bytes(keccak256(preimage).0)
+        });
+        // Slots wrap around as in the EVM.
+        slot.wrapping_add(self.offset)
+    }
+
+    /// Reads the value for the account at the snapshot block, unpacking it from its slot.
+    fn read<E: StrategyEnv>(
+        &self,
+        env: &mut E,
+        layout: Layout,
+        contract: Address,
+        account: Address,
+    ) -> Result<U256, StrategyError> {
+        let word = env.storage(contract, self.slot_of(layout, account))?;
+        let mask = U256::MAX >> (256 - self.bits);
+        Ok((word >> self.shift) & mask)
+    }
+}
+
+/// Voting power read from the storage of the asset contract at the snapshot block, for tokens
+/// without a view function of their historical balances, or whose getters cost too many cycles.
+///
+/// The slot of the balance of an account is derived from the base slot of its mapping, as laid
+/// out by Solidity or Vyper, with the account or constants as the keys of nested mappings. The
+/// total supply is read from its slot the same way.
+pub struct StorageSlot;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for StorageSlot {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params = StorageSlotParams::parse(&asset.params)?;
+        params
+            .balance
+            .read(env, params.layout, asset.contract, account)
+    }
+
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params = StorageSlotParams::parse(&asset.params)?;
+        params
+            .supply
+            .read(env, params.layout, asset.contract, Address::ZERO)
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        StorageSlotParams::parse(params).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::MockEnv;
+    use alloy_primitives::{address, uint};
+
+    const ACCOUNT: Address = address!("1111111111111111111111111111111111111111");
+
+    fn slot_params(slot: u64, keys: Vec<MappingKey>) -> SlotParams {
+        SlotParams {
+            slot: U256::from(slot),
+            keys,
+            offset: U256::ZERO,
+            shift: 0,
+            bits: 256,
+        }
+    }
+
+    #[test]
+    fn slot_of_a_solidity_balance() {
+        // `mapping(address => uint256) balanceOf` at slot 0.
+        let balance = slot_params(0, vec![MappingKey::Account]);
+        assert_eq!(
+            balance.slot_of(Layout::Solidity, ACCOUNT),
+            uint!(0xf043c50fe795c69f30b8ff78b84032dc53a9d87ca283ae10a1dacfbb648e83ef_U256)
+        );
+    }
+
+    #[test]
+    fn slot_of_a_field_in_a_nested_mapping() {
+        // Second field of the struct of `mapping(uint256 => mapping(address => Checkpoint))` at
+        // slot 5, for the key 7.
+        let balance = SlotParams {
+            offset: U256::from(1),
+            ..slot_params(
+                5,
+                vec![MappingKey::Constant(U256::from(7)), MappingKey::Account],
+            )
+        };
+        assert_eq!(
+            balance.slot_of(Layout::Solidity, ACCOUNT),
+            uint!(0xba85390d0fde63f8e56cfe07d2c71cc1c2b66eaee4d62e8d892e1591acf97547_U256)
+        );
+    }
+
+    #[test]
+    fn slot_of_a_vyper_balance() {
+        // `balanceOf: HashMap[address, uint256]` at slot 3.
+        let balance = slot_params(3, vec![MappingKey::Account]);
+        assert_eq!(
+            balance.slot_of(Layout::Vyper, ACCOUNT),
+            uint!(0x8d880ad96f277cf5638a19b870e892186320934ff5ede8fb56daeccc05010c06_U256)
+        );
+    }
+
+    #[test]
+    fn reads_a_value_packed_in_a_slot() {
+        let contract = Address::repeat_byte(0x01);
+        // A `uint96` packed after an `address` in slot 2.
+        let packed = SlotParams {
+            shift: 160,
+            bits: 96,
+            ..slot_params(2, Vec::new())
+        };
+        let mut env = MockEnv::default();
+        env.mock_storage(
+            contract,
+            U256::from(2),
+            (U256::from(1234) << 160) | U256::from_be_slice(ACCOUNT.as_slice()),
+        );
+        assert_eq!(
+            packed
+                .read(&mut env, Layout::Solidity, contract, ACCOUNT)
+                .unwrap(),
+            U256::from(1234)
+        );
+
+        let low = SlotParams {
+            bits: 160,
+            ..slot_params(2, Vec::new())
+        };
+        assert_eq!(
+            low.read(&mut env, Layout::Solidity, contract, ACCOUNT)
+                .unwrap(),
+            U256::from_be_slice(ACCOUNT.as_slice())
+        );
+    }
+}
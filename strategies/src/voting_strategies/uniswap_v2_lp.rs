@@ -0,0 +1,126 @@
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::sol;
+use serde::Deserialize;
+
+sol! {
+    /// Uniswap V2 pair function signatures.
+    interface IUniswapV2Pair {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast);
+        function balanceOf(address owner) external view returns (uint256);
+        function totalSupply() external view returns (uint256);
+    }
+    /// MasterChef-style farm, with a pool per staked token.
+    interface IMasterChef {
+        function userInfo(uint256 pid, address user) external view returns (uint256 amount, uint256 rewardDebt);
+    }
+}
+
+sol! {
+    /// Farm whose stake is an ERC-20 receipt of the LP tokens.
+    interface IFarm {
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct UniswapV2LpParams {
+    /// Pair of the governance token, which is the asset contract.
+    pair: Address,
+    /// Farm in which the LP tokens can be staked.
+    #[serde(default)]
+    farm: Option<Farm>,
+}
+
+#[derive(Deserialize)]
+#[serde(
+    tag = "interface",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum Farm {
+    BalanceOf { contract: Address },
+    UserInfo { contract: Address, pool_id: U256 },
+}
+
+/// Reserve of the governance token in the pair, and total supply of the LP token.
+fn pool_state<E: StrategyEnv>(
+    env: &mut E,
+    token: Address,
+    pair: Address,
+) -> Result<(U256, U256), StrategyError> {
+    let reserves = env.call(pair, &IUniswapV2Pair::getReservesCall {})?;
+    let reserve = if env.call(pair, &IUniswapV2Pair::token0Call {})?._0 == token {
+        U256::from(reserves.reserve0)
+    } else if env.call(pair, &IUniswapV2Pair::token1Call {})?._0 == token {
+        U256::from(reserves.reserve1)
+    } else {
+        return Err(StrategyError::InvalidParams(format!(
+            "pair {} does not hold token {}",
+            pair, token
+        )));
+    };
+    let total_supply = env.call(pair, &IUniswapV2Pair::totalSupplyCall {})?._0;
+    Ok((reserve, total_supply))
+}
+
+/// Voting power of the governance token provided as liquidity to a Uniswap V2 pair, as the share
+/// of the token reserve owned through the LP tokens held or staked in a farm.
+pub struct UniswapV2Lp;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for UniswapV2Lp {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params: UniswapV2LpParams = parse_params(&asset.params)?;
+        let balance_call = IUniswapV2Pair::balanceOfCall { owner: account };
+        let mut lp_balance = env.call(params.pair, &balance_call)?._0;
+        let staked = match params.farm {
+            None => U256::from(0),
+            Some(Farm::BalanceOf { contract }) => {
+                let balance_call = IFarm::balanceOfCall { account };
+                env.call(contract, &balance_call)?._0
+            }
+            Some(Farm::UserInfo { contract, pool_id }) => {
+                let user_info_call = IMasterChef::userInfoCall {
+                    pid: pool_id,
+                    user: account,
+                };
+                env.call(contract, &user_info_call)?.amount
+            }
+        };
+        lp_balance = lp_balance
+            .checked_add(staked)
+            .ok_or(StrategyError::ArithmeticOverflow)?;
+
+        let (reserve, total_supply) = pool_state(env, asset.contract, params.pair)?;
+        if total_supply.is_zero() {
+            return Ok(U256::from(0));
+        }
+        Ok(lp_balance
+            .checked_mul(reserve)
+            .ok_or(StrategyError::ArithmeticOverflow)?
+            / total_supply)
+    }
+
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params: UniswapV2LpParams = parse_params(&asset.params)?;
+        let (reserve, _) = pool_state(env, asset.contract, params.pair)?;
+        Ok(reserve)
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        parse_params::<UniswapV2LpParams>(params).map(|_| ())
+    }
+}
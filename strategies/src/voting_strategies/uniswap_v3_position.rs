@@ -0,0 +1,438 @@
+use super::erc721_owner_of::{check_owner, decode_token_ids, tokens_of_owner};
+use super::shared::balance_of;
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{uint, Address, Bytes, U256, U512};
+use alloy_sol_types::{sol, SolValue};
+use serde::Deserialize;
+
+sol! {
+    /// Uniswap V3 `NonfungiblePositionManager` position function signature.
+    interface INonfungiblePositionManager {
+        function positions(uint256 tokenId) external view returns (uint96 nonce, address operator, address token0, address token1, uint24 fee, int24 tickLower, int24 tickUpper, uint128 liquidity, uint256 feeGrowthInside0LastX128, uint256 feeGrowthInside1LastX128, uint128 tokensOwed0, uint128 tokensOwed1);
+    }
+    /// Uniswap V3 pool function signatures.
+    interface IUniswapV3Pool {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function fee() external view returns (uint24);
+        function slot0() external view returns (uint160 sqrtPriceX96, int24 tick, uint16 observationIndex, uint16 observationCardinality, uint16 observationCardinalityNext, uint8 feeProtocol, bool unlocked);
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct UniswapV3PositionParams {
+    position_manager: Address,
+    /// Pool of the governance token, which is the asset contract.
+    pool: Address,
+}
+
+const MIN_TICK: i32 = -887272;
+const MAX_TICK: i32 = 887272;
+
+/// Square root price, as a Q64.96, at a tick. Port of `TickMath.getSqrtRatioAtTick`.
+fn sqrt_ratio_at_tick(tick: i32) -> Result<U256, StrategyError> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(StrategyError::InvalidHint(format!(
+            "tick {} is out of range",
+            tick
+        )));
+    }
+    let abs_tick = tick.unsigned_abs();
+    const FACTORS: [U256; 19] = [
+        uint!(0xfff97272373d413259a46990580e213a_U256),
+        uint!(0xfff2e50f5f656932ef12357cf3c7fdcc_U256),
+        uint!(0xffe5caca7e10e4e61c3624eaa0941cd0_U256),
+        uint!(0xffcb9843d60f6159c9db58835c926644_U256),
+        uint!(0xff973b41fa98c081472e6896dfb254c0_U256),
+        uint!(0xff2ea16466c96a3843ec78b326b52861_U256),
+        uint!(0xfe5dee046a99a2a811c461f1969c3053_U256),
+        uint!(0xfcbe86c7900a88aedcffc83b479aa3a4_U256),
+        uint!(0xf987a7253ac413176f2b074cf7815e54_U256),
+        uint!(0xf3392b0822b70005940c7a398e4b70f3_U256),
+        uint!(0xe7159475a2c29b7443b29c7fa6e889d9_U256),
+        uint!(0xd097f3bdfd2022b8845ad8f792aa5825_U256),
+        uint!(0xa9f746462d870fdf8a65dc1f90e061e5_U256),
+        uint!(0x70d869a156d2a1b890bb3df62baf32f7_U256),
+        uint!(0x31be135f97d08fd981231505542fcfa6_U256),
+        uint!(0x9aa508b5b7a84e1c677de54f3e99bc9_U256),
+        uint!(0x5d6af8dedb81196699c329225ee604_U256),
+        uint!(0x2216e584f5fa1ea926041bedfe98_U256),
+        uint!(0x48a170391f7dc42444e8fa2_U256),
+    ];
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        uint!(0xfffcb933bd6fad37aa2d162d1a594001_U256)
+    } else {
+        uint!(0x100000000000000000000000000000000_U256)
+    };
+    for (bit, factor) in FACTORS.iter().enumerate() {
+        if abs_tick & (0x2 << bit) != 0 {
+            ratio = ratio.wrapping_mul(*factor) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+    // Rounds up, so that the price at a tick is at least the one of the tick.
+    let round_up = if (ratio & U256::from(u32::MAX)).is_zero() {
+        U256::from(0)
+    } else {
+        U256::from(1)
+    };
+    Ok((ratio >> 32) + round_up)
+}
+
+/// `a * b / denominator` with a full precision intermediate product. Port of `FullMath.mulDiv`.
+fn mul_div(a: U256, b: U256, denominator: U256) -> Result<U256, StrategyError> {
+    if denominator.is_zero() {
+        return Err(StrategyError::ArithmeticOverflow);
+    }
+    let quotient = U512::from(a) * U512::from(b) / U512::from(denominator);
+    if quotient > U512::from(U256::MAX) {
+        return Err(StrategyError::ArithmeticOverflow);
+    }
+    Ok(U256::from_limbs_slice(&quotient.as_limbs()[..4]))
+}
+
+/// Amount of token0 of a liquidity between two prices. Port of
+/// `LiquidityAmounts.getAmount0ForLiquidity`.
+fn amount0_for_liquidity(
+    sqrt_ratio_a: U256,
+    sqrt_ratio_b: U256,
+    liquidity: u128,
+) -> Result<U256, StrategyError> {
+    let (sqrt_ratio_a, sqrt_ratio_b) = if sqrt_ratio_a > sqrt_ratio_b {
+        (sqrt_ratio_b, sqrt_ratio_a)
+    } else {
+        (sqrt_ratio_a, sqrt_ratio_b)
+    };
+    Ok(mul_div(
+        U256::from(liquidity) << 96,
+        sqrt_ratio_b - sqrt_ratio_a,
+        sqrt_ratio_b,
+    )? / sqrt_ratio_a)
+}
+
+/// Amount of token1 of a liquidity between two prices. Port of
+/// `LiquidityAmounts.getAmount1ForLiquidity`.
+fn amount1_for_liquidity(
+    sqrt_ratio_a: U256,
+    sqrt_ratio_b: U256,
+    liquidity: u128,
+) -> Result<U256, StrategyError> {
+    let (sqrt_ratio_a, sqrt_ratio_b) = if sqrt_ratio_a > sqrt_ratio_b {
+        (sqrt_ratio_b, sqrt_ratio_a)
+    } else {
+        (sqrt_ratio_a, sqrt_ratio_b)
+    };
+    mul_div(
+        U256::from(liquidity),
+        sqrt_ratio_b - sqrt_ratio_a,
+        U256::from(1) << 96,
+    )
+}
+
+/// Amounts of token0 and token1 of a liquidity at the current price of the pool. Port of
+/// `LiquidityAmounts.getAmountsForLiquidity`.
+fn amounts_for_liquidity(
+    sqrt_ratio: U256,
+    sqrt_ratio_a: U256,
+    sqrt_ratio_b: U256,
+    liquidity: u128,
+) -> Result<(U256, U256), StrategyError> {
+    let (sqrt_ratio_a, sqrt_ratio_b) = if sqrt_ratio_a > sqrt_ratio_b {
+        (sqrt_ratio_b, sqrt_ratio_a)
+    } else {
+        (sqrt_ratio_a, sqrt_ratio_b)
+    };
+    if sqrt_ratio <= sqrt_ratio_a {
+        Ok((
+            amount0_for_liquidity(sqrt_ratio_a, sqrt_ratio_b, liquidity)?,
+            U256::from(0),
+        ))
+    } else if sqrt_ratio < sqrt_ratio_b {
+        Ok((
+            amount0_for_liquidity(sqrt_ratio, sqrt_ratio_b, liquidity)?,
+            amount1_for_liquidity(sqrt_ratio_a, sqrt_ratio, liquidity)?,
+        ))
+    } else {
+        Ok((
+            U256::from(0),
+            amount1_for_liquidity(sqrt_ratio_a, sqrt_ratio_b, liquidity)?,
+        ))
+    }
+}
+
+/// Tokens and fee identifying a pool, which the positions of the pool share.
+struct PoolKey {
+    token0: Address,
+    token1: Address,
+    fee: u32,
+}
+
+impl PoolKey {
+    fn read<E: StrategyEnv>(env: &mut E, pool: Address) -> Result<Self, StrategyError> {
+        Ok(Self {
+            token0: env.call(pool, &IUniswapV3Pool::token0Call {})?._0,
+            token1: env.call(pool, &IUniswapV3Pool::token1Call {})?._0,
+            fee: env.call(pool, &IUniswapV3Pool::feeCall {})?._0,
+        })
+    }
+
+    fn contains(&self, position: &INonfungiblePositionManager::positionsReturn) -> bool {
+        position.token0 == self.token0 && position.token1 == self.token1 && position.fee == self.fee
+    }
+}
+
+fn tick(tick: impl TryInto<i32>) -> Result<i32, StrategyError> {
+    tick.try_into()
+        .map_err(|_| StrategyError::InvalidHint("tick is out of range".to_string()))
+}
+
+/// Voting power of the governance token provided as liquidity to a Uniswap V3 pool, as the
+/// amount of the token in the positions of the account at the current price of the pool.
+///
+/// The hint of an account is the ABI encoded `uint256[]` of the ids of its positions, whose
+/// ownership is checked with the `NonfungiblePositionManager` at the snapshot block. The fees
+/// owed to the positions are not counted.
+pub struct UniswapV3Position;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for UniswapV3Position {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params: UniswapV3PositionParams = parse_params(&asset.params)?;
+        let ids = decode_token_ids(hint)?;
+        if ids.is_empty() {
+            return Ok(U256::from(0));
+        }
+
+        let pool = PoolKey::read(env, params.pool)?;
+        if asset.contract != pool.token0 && asset.contract != pool.token1 {
+            return Err(StrategyError::InvalidParams(format!(
+                "pool {} does not hold token {}",
+                params.pool, asset.contract
+            )));
+        }
+        let sqrt_price = U256::from(
+            env.call(params.pool, &IUniswapV3Pool::slot0Call {})?
+                .sqrtPriceX96,
+        );
+
+        ids.iter().try_fold(U256::from(0), |acc, id| {
+            check_owner(env, params.position_manager, *id, account)?;
+            let positions_call = INonfungiblePositionManager::positionsCall { tokenId: *id };
+            let position = env.call(params.position_manager, &positions_call)?;
+            if !pool.contains(&position) {
+                return Err(StrategyError::InvalidHint(format!(
+                    "position {} is not in pool {}",
+                    id, params.pool
+                )));
+            }
+            let (amount0, amount1) = amounts_for_liquidity(
+                sqrt_price,
+                sqrt_ratio_at_tick(tick(position.tickLower)?)?,
+                sqrt_ratio_at_tick(tick(position.tickUpper)?)?,
+                position.liquidity,
+            )?;
+            let amount = if asset.contract == pool.token0 {
+                amount0
+            } else {
+                amount1
+            };
+            acc.checked_add(amount)
+                .ok_or(StrategyError::ArithmeticOverflow)
+        })
+    }
+
+    /// The balance of the pool, which bounds the amounts of all of its positions.
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params: UniswapV3PositionParams = parse_params(&asset.params)?;
+        balance_of(env, asset.contract, params.pool)
+    }
+
+    /// Lists the positions of the account in the pool, enumerated by the
+    /// `NonfungiblePositionManager`, which also holds the positions of the other pools.
+    fn hint(&self, env: &mut E, account: Address, asset: &Asset) -> Result<Bytes, StrategyError> {
+        let params: UniswapV3PositionParams = parse_params(&asset.params)?;
+        let pool = PoolKey::read(env, params.pool)?;
+        let mut ids = Vec::new();
+        for id in tokens_of_owner(env, params.position_manager, account)? {
+            let positions_call = INonfungiblePositionManager::positionsCall { tokenId: id };
+            if pool.contains(&env.call(params.position_manager, &positions_call)?) {
+                ids.push(id);
+            }
+        }
+        Ok(ids.abi_encode().into())
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        parse_params::<UniswapV3PositionParams>(params).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::erc721_owner_of::IERC721;
+    use super::*;
+    use crate::environment::MockEnv;
+    use alloy_primitives::I256;
+
+    #[test]
+    fn sqrt_ratio_at_tick_matches_tick_math() {
+        // Values of `TickMath.getSqrtRatioAtTick`.
+        let cases: [(i32, U256); 10] = [
+            (MIN_TICK, uint!(4295128739_U256)),
+            (MIN_TICK + 1, uint!(4295343490_U256)),
+            (-50000, uint!(6504256538020985011912221507_U256)),
+            (0, U256::from(1) << 96),
+            (50, uint!(79426470787362580746886972461_U256)),
+            (100, uint!(79625275426524748796330556128_U256)),
+            (1000, uint!(83290069058676223003182343270_U256)),
+            (50000, uint!(965075977353221155028623082916_U256)),
+            (
+                MAX_TICK - 1,
+                uint!(1461373636630004318706518188784493106690254656249_U256),
+            ),
+            (
+                MAX_TICK,
+                uint!(1461446703485210103287273052203988822378723970342_U256),
+            ),
+        ];
+        for (tick, sqrt_ratio) in cases {
+            assert_eq!(
+                sqrt_ratio_at_tick(tick).unwrap(),
+                sqrt_ratio,
+                "tick {}",
+                tick
+            );
+        }
+    }
+
+    #[test]
+    fn sqrt_ratio_at_tick_rejects_ticks_out_of_range() {
+        assert!(sqrt_ratio_at_tick(MIN_TICK - 1).is_err());
+        assert!(sqrt_ratio_at_tick(MAX_TICK + 1).is_err());
+    }
+
+    #[test]
+    fn amounts_for_liquidity_match_liquidity_amounts() {
+        // Values of `LiquidityAmounts.getAmountsForLiquidity` for a liquidity of 10^18, as
+        // `(tick, tickLower, tickUpper, amount0, amount1)`.
+        let cases: [(i32, i32, i32, u128, u128); 5] = [
+            (0, -60, 60, 2995354955910780, 2995354955910780),
+            (-120, -60, 60, 5999709018652706, 0),
+            (120, -60, 60, 0, 5999709018652706),
+            (0, -887220, 887220, 999999999999999999, 999999999999999999),
+            (1000, -1000, 2000, 46389860485952232, 100036665958045479),
+        ];
+        for (tick, tick_lower, tick_upper, amount0, amount1) in cases {
+            let amounts = amounts_for_liquidity(
+                sqrt_ratio_at_tick(tick).unwrap(),
+                sqrt_ratio_at_tick(tick_lower).unwrap(),
+                sqrt_ratio_at_tick(tick_upper).unwrap(),
+                1_000_000_000_000_000_000,
+            )
+            .unwrap();
+            assert_eq!(
+                amounts,
+                (U256::from(amount0), U256::from(amount1)),
+                "tick {} in [{}, {}]",
+                tick,
+                tick_lower,
+                tick_upper
+            );
+        }
+    }
+
+    #[test]
+    fn mul_div_keeps_the_full_precision_product() {
+        assert_eq!(mul_div(U256::MAX, U256::MAX, U256::MAX).unwrap(), U256::MAX);
+        assert!(mul_div(U256::MAX, U256::from(2), U256::from(1)).is_err());
+        assert!(mul_div(U256::from(1), U256::from(1), U256::ZERO).is_err());
+    }
+
+    #[test]
+    fn hint_lists_the_positions_of_the_pool_only() {
+        let position_manager = Address::repeat_byte(0x01);
+        let pool = Address::repeat_byte(0x02);
+        let token = Address::repeat_byte(0x03);
+        let weth = Address::repeat_byte(0x04);
+        let other_token = Address::repeat_byte(0x05);
+        let account = Address::repeat_byte(0x06);
+
+        let mut env = MockEnv::default();
+        env.mock_call(pool, &IUniswapV3Pool::token0Call {}, token.abi_encode());
+        env.mock_call(pool, &IUniswapV3Pool::token1Call {}, weth.abi_encode());
+        env.mock_call(
+            pool,
+            &IUniswapV3Pool::feeCall {},
+            U256::from(3000).abi_encode(),
+        );
+        // Positions of the pool, of another fee tier and of another pair.
+        let positions = [
+            (1, token, weth, 3000),
+            (2, token, weth, 500),
+            (3, token, other_token, 3000),
+            (4, token, weth, 3000),
+        ];
+        env.mock_call(
+            position_manager,
+            &IERC721::balanceOfCall { owner: account },
+            U256::from(positions.len()).abi_encode(),
+        );
+        for (index, (id, token0, token1, fee)) in positions.into_iter().enumerate() {
+            env.mock_call(
+                position_manager,
+                &IERC721::tokenOfOwnerByIndexCall {
+                    owner: account,
+                    index: U256::from(index),
+                },
+                U256::from(id).abi_encode(),
+            );
+            env.mock_call(
+                position_manager,
+                &INonfungiblePositionManager::positionsCall {
+                    tokenId: U256::from(id),
+                },
+                (
+                    U256::ZERO,
+                    Address::ZERO,
+                    token0,
+                    token1,
+                    U256::from(fee),
+                    I256::try_from(-60).unwrap(),
+                    I256::try_from(60).unwrap(),
+                    U256::from(1_000_000),
+                    U256::ZERO,
+                    U256::ZERO,
+                    U256::ZERO,
+                    U256::ZERO,
+                )
+                    .abi_encode_params(),
+            );
+        }
+
+        let asset: Asset = serde_json::from_value(serde_json::json!({
+            "contract": token,
+            "chainId": 1,
+            "votingPowerStrategy": "UniswapV3Position",
+            "params": { "positionManager": position_manager, "pool": pool },
+            "delegation": { "contract": Address::ZERO, "strategy": "SplitDelegation" },
+        }))
+        .unwrap();
+        let hint = UniswapV3Position.hint(&mut env, account, &asset).unwrap();
+        assert_eq!(
+            decode_token_ids(&hint).unwrap(),
+            vec![U256::from(1), U256::from(4)]
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use super::shared::{balance_of, total_supply, SupplySource};
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::sol;
+use risc0_steel::EvmBlockHeader;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+sol! {
+    /// OpenZeppelin `VestingWallet` function signatures.
+    interface IVestingWallet {
+        function released(address token) external view returns (uint256);
+        function vestedAmount(address token, uint64 timestamp) external view returns (uint256);
+    }
+    /// Registry listing the vesting wallets of a beneficiary.
+    interface IVestingRegistry {
+        function walletsOf(address beneficiary) external view returns (address[]);
+    }
+    /// Factory deploying a single vesting wallet per beneficiary.
+    interface IVestingFactory {
+        function walletOf(address beneficiary) external view returns (address);
+    }
+}
+
+/// Weight of a whole voting power, in basis points.
+const FULL_WEIGHT: u64 = 10_000;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct VestingParams {
+    wallets: WalletSource,
+    /// Weight, in basis points, of the vested tokens not released to the beneficiary yet.
+    #[serde(default = "full_weight")]
+    vested_weight: u64,
+    /// Weight, in basis points, of the tokens still vesting.
+    #[serde(default = "full_weight")]
+    unvested_weight: u64,
+    /// Total supply of the token, weighted by the greater of the two weights, or a weighted
+    /// supply declared in the config.
+    supply: SupplySource,
+}
+
+fn full_weight() -> u64 {
+    FULL_WEIGHT
+}
+
+/// Contract through which the vesting wallets of a beneficiary are found.
+#[derive(Deserialize)]
+#[serde(tag = "interface", rename_all = "camelCase")]
+enum WalletSource {
+    /// Registry implementing `walletsOf(address)`.
+    WalletsOf { contract: Address },
+    /// Factory implementing `walletOf(address)`, which returns the zero address for accounts
+    /// without wallet.
+    WalletOf { contract: Address },
+}
+
+impl VestingParams {
+    fn parse(params: &StrategyParams) -> Result<Self, StrategyError> {
+        let params: Self = parse_params(params)?;
+        if params.vested_weight == 0 && params.unvested_weight == 0 {
+            return Err(StrategyError::InvalidParams(
+                "vested and unvested weights must not both be zero".to_string(),
+            ));
+        }
+        Ok(params)
+    }
+}
+
+/// Vesting wallets of the beneficiary at the snapshot block. A wallet listed more than once by a
+/// registry is only returned once, so that its tokens are not counted twice.
+fn wallets_of<E: StrategyEnv>(
+    env: &mut E,
+    source: &WalletSource,
+    beneficiary: Address,
+) -> Result<Vec<Address>, StrategyError> {
+    match source {
+        WalletSource::WalletsOf { contract } => {
+            let wallets_call = IVestingRegistry::walletsOfCall { beneficiary };
+            let mut listed = HashSet::new();
+            Ok(env
+                .call(*contract, &wallets_call)?
+                ._0
+                .into_iter()
+                .filter(|wallet| listed.insert(*wallet))
+                .collect())
+        }
+        WalletSource::WalletOf { contract } => {
+            let wallet_call = IVestingFactory::walletOfCall { beneficiary };
+            let wallet = env.call(*contract, &wallet_call)?._0;
+            Ok(if wallet.is_zero() {
+                Vec::new()
+            } else {
+                vec![wallet]
+            })
+        }
+    }
+}
+
+/// Vested but unreleased, and unvested amounts of the token in a wallet at `timestamp`.
+fn vesting_amounts<E: StrategyEnv>(
+    env: &mut E,
+    wallet: Address,
+    token: Address,
+    timestamp: u64,
+) -> Result<(U256, U256), StrategyError> {
+    let released = env
+        .call(wallet, &IVestingWallet::releasedCall { token })?
+        ._0;
+    let vested_call = IVestingWallet::vestedAmountCall { token, timestamp };
+    let vested = env.call(wallet, &vested_call)?._0;
+    let allocation = balance_of(env, token, wallet)?
+        .checked_add(released)
+        .ok_or(StrategyError::ArithmeticOverflow)?;
+    Ok((
+        vested.saturating_sub(released),
+        allocation.saturating_sub(vested),
+    ))
+}
+
+/// Voting power of the tokens of the beneficiary in linear vesting wallets, such as
+/// OpenZeppelin's `VestingWallet`, at the timestamp of the snapshot block. The asset contract is
+/// the vested token.
+///
+/// The vested tokens not released yet and the tokens still vesting are weighted separately.
+/// Released tokens are held by the beneficiary, and are not counted.
+pub struct VestingWallet;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for VestingWallet {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params = VestingParams::parse(&asset.params)?;
+        let timestamp = env.header().timestamp();
+        let mut vested = U256::from(0);
+        let mut unvested = U256::from(0);
+        for wallet in wallets_of(env, &params.wallets, account)? {
+            let (wallet_vested, wallet_unvested) =
+                vesting_amounts(env, wallet, asset.contract, timestamp)?;
+            vested = vested
+                .checked_add(wallet_vested)
+                .ok_or(StrategyError::ArithmeticOverflow)?;
+            unvested = unvested
+                .checked_add(wallet_unvested)
+                .ok_or(StrategyError::ArithmeticOverflow)?;
+        }
+        let vested = vested.checked_mul(U256::from(params.vested_weight));
+        let unvested = unvested.checked_mul(U256::from(params.unvested_weight));
+        vested
+            .zip(unvested)
+            .and_then(|(vested, unvested)| vested.checked_add(unvested))
+            .map(|power| power / U256::from(FULL_WEIGHT))
+            .ok_or(StrategyError::ArithmeticOverflow)
+    }
+
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params = VestingParams::parse(&asset.params)?;
+        match params.supply {
+            SupplySource::TotalSupply => {
+                let total_supply = total_supply(env, asset.contract)?;
+                let weight = params.vested_weight.max(params.unvested_weight);
+                total_supply
+                    .checked_mul(U256::from(weight))
+                    .map(|supply| supply / U256::from(FULL_WEIGHT))
+                    .ok_or(StrategyError::ArithmeticOverflow)
+            }
+            SupplySource::Fixed { amount } => Ok(amount),
+        }
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        VestingParams::parse(params).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::MockEnv;
+    use alloy_sol_types::SolValue;
+
+    #[test]
+    fn wallets_listed_more_than_once_are_returned_once() {
+        let registry = Address::repeat_byte(0x01);
+        let beneficiary = Address::repeat_byte(0x02);
+        let (first, second) = (Address::repeat_byte(0x03), Address::repeat_byte(0x04));
+        let mut env = MockEnv::default();
+        env.mock_call(
+            registry,
+            &IVestingRegistry::walletsOfCall { beneficiary },
+            vec![first, second, first, second].abi_encode(),
+        );
+
+        let source = WalletSource::WalletsOf { contract: registry };
+        assert_eq!(
+            wallets_of(&mut env, &source, beneficiary).unwrap(),
+            vec![first, second]
+        );
+    }
+}
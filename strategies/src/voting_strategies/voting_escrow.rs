@@ -0,0 +1,85 @@
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::sol;
+use risc0_steel::EvmBlockHeader;
+use serde::Deserialize;
+
+sol! {
+    /// Curve-style vote-escrow function signatures.
+    interface IVotingEscrow {
+        function locked(address account) external view returns (int128 amount, uint256 end);
+        function totalSupply(uint256 t) external view returns (uint256);
+    }
+}
+
+/// Four years, the maximum lock time of Curve's vote-escrow.
+const DEFAULT_MAX_LOCK_TIME: u64 = 4 * 365 * 86400;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct VotingEscrowParams {
+    /// Lock time, in seconds, granting a voting power equal to the locked amount.
+    #[serde(default = "default_max_lock_time")]
+    max_lock_time: u64,
+}
+
+fn default_max_lock_time() -> u64 {
+    DEFAULT_MAX_LOCK_TIME
+}
+
+impl VotingEscrowParams {
+    fn parse(params: &StrategyParams) -> Result<Self, StrategyError> {
+        let params: Self = parse_params(params)?;
+        if params.max_lock_time == 0 {
+            return Err(StrategyError::InvalidParams(
+                "max lock time must not be zero".to_string(),
+            ));
+        }
+        Ok(params)
+    }
+}
+
+/// Decaying voting power of the tokens locked in a vote-escrow, at the timestamp of the snapshot
+/// block. The asset contract is the escrow.
+///
+/// The power is `amount / maxLockTime * (end - timestamp)`, rounded as the slope of the lock in
+/// the escrow, so that the voting powers add up to the escrow's `totalSupply(timestamp)`.
+pub struct VotingEscrow;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for VotingEscrow {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params = VotingEscrowParams::parse(&asset.params)?;
+        let timestamp = U256::from(env.header().timestamp());
+        let locked_call = IVotingEscrow::lockedCall { account };
+        let locked = env.call(asset.contract, &locked_call)?;
+        // Expired locks keep their amount until withdrawn, without voting power.
+        if locked.end <= timestamp || locked.amount <= 0 {
+            return Ok(U256::from(0));
+        }
+        let slope = U256::from(locked.amount as u128) / U256::from(params.max_lock_time);
+        slope
+            .checked_mul(locked.end - timestamp)
+            .ok_or(StrategyError::ArithmeticOverflow)
+    }
+
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let total_supply_call = IVotingEscrow::totalSupplyCall {
+            t: U256::from(env.header().timestamp()),
+        };
+        Ok(env.call(asset.contract, &total_supply_call)?._0)
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        VotingEscrowParams::parse(params).map(|_| ())
+    }
+}
@@ -0,0 +1,156 @@
+use super::shared::{balance_of, total_supply};
+use super::VotingPowerStrategy;
+use crate::{
+    environment::StrategyEnv,
+    params::{parse_params, StrategyParams},
+    Asset, StrategyError,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::sol;
+use serde::Deserialize;
+
+sol! {
+    /// ERC-4626 vault conversion of shares.
+    interface IERC4626 {
+        function convertToAssets(uint256 shares) external view returns (uint256);
+    }
+    /// Lido's wstETH conversion of wrapped tokens.
+    interface IWstETH {
+        function getStETHByWstETH(uint256 wstETHAmount) external view returns (uint256);
+    }
+    /// Yearn-style vault price of a share.
+    interface IVault {
+        function pricePerShare() external view returns (uint256);
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct WrappedBalanceParams {
+    /// Chain of wrappers, each wrapping the previous one: the first wraps the asset token, and
+    /// the balances of the last one are counted.
+    wrappers: Vec<Wrapper>,
+    /// Whether the tokens held unwrapped count as well.
+    #[serde(default)]
+    include_underlying: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct Wrapper {
+    contract: Address,
+    #[serde(default)]
+    rate: RateFunction,
+}
+
+/// Function converting an amount of a wrapper to the amount of the token it wraps.
+#[derive(Deserialize, Default)]
+#[serde(
+    tag = "function",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum RateFunction {
+    /// `convertToAssets(uint256)` of ERC-4626 vaults.
+    #[default]
+    ConvertToAssets,
+    /// `getStETHByWstETH(uint256)` of Lido's wstETH.
+    GetStETHByWstETH,
+    /// `pricePerShare()`, the amount of the wrapped token of `scale` shares.
+    PricePerShare { scale: U256 },
+}
+
+impl WrappedBalanceParams {
+    fn parse(params: &StrategyParams) -> Result<Self, StrategyError> {
+        let params: Self = parse_params(params)?;
+        if params.wrappers.is_empty() {
+            return Err(StrategyError::InvalidParams(
+                "at least one wrapper is required".to_string(),
+            ));
+        }
+        let zero_scale = params.wrappers.iter().find(|wrapper| {
+            matches!(wrapper.rate, RateFunction::PricePerShare { scale } if scale.is_zero())
+        });
+        if let Some(wrapper) = zero_scale {
+            return Err(StrategyError::InvalidParams(format!(
+                "the price per share scale of wrapper {} must not be zero",
+                wrapper.contract
+            )));
+        }
+        Ok(params)
+    }
+
+    /// Converts an amount of the outermost wrapper to the asset token, unwrapping it through
+    /// every wrapper of the chain at the snapshot block.
+    fn unwrap<E: StrategyEnv>(&self, env: &mut E, amount: U256) -> Result<U256, StrategyError> {
+        self.wrappers
+            .iter()
+            .rev()
+            .try_fold(amount, |amount, wrapper| match wrapper.rate {
+                RateFunction::ConvertToAssets => {
+                    let convert_call = IERC4626::convertToAssetsCall { shares: amount };
+                    Ok(env.call(wrapper.contract, &convert_call)?._0)
+                }
+                RateFunction::GetStETHByWstETH => {
+                    let convert_call = IWstETH::getStETHByWstETHCall {
+                        wstETHAmount: amount,
+                    };
+                    Ok(env.call(wrapper.contract, &convert_call)?._0)
+                }
+                RateFunction::PricePerShare { scale } => {
+                    let price = env
+                        .call(wrapper.contract, &IVault::pricePerShareCall {})?
+                        ._0;
+                    Ok(amount
+                        .checked_mul(price)
+                        .ok_or(StrategyError::ArithmeticOverflow)?
+                        / scale)
+                }
+            })
+    }
+
+    fn outermost(&self) -> Address {
+        self.wrappers[self.wrappers.len() - 1].contract
+    }
+}
+
+/// Voting power of the asset token wrapped in ERC-4626 vaults or wstETH-style wrappers, as the
+/// balance of the outermost wrapper converted to the token at the snapshot block.
+///
+/// Conversions round down, so the voting powers add up to at most the supply, which is the
+/// total supply of the outermost wrapper converted the same way.
+pub struct WrappedBalanceOf;
+impl<E: StrategyEnv> VotingPowerStrategy<E> for WrappedBalanceOf {
+    fn process(
+        &self,
+        env: &mut E,
+        account: Address,
+        asset: &Asset,
+        _hint: &Bytes,
+    ) -> Result<U256, StrategyError> {
+        let params = WrappedBalanceParams::parse(&asset.params)?;
+        let wrapped = balance_of(env, params.outermost(), account)?;
+        let wrapped = params.unwrap(env, wrapped)?;
+        if !params.include_underlying {
+            return Ok(wrapped);
+        }
+        let underlying = balance_of(env, asset.contract, account)?;
+        wrapped
+            .checked_add(underlying)
+            .ok_or(StrategyError::ArithmeticOverflow)
+    }
+
+    fn get_supply(&self, env: &mut E, asset: &Asset) -> Result<U256, StrategyError> {
+        let params = WrappedBalanceParams::parse(&asset.params)?;
+        if params.include_underlying {
+            // Unwrapping the supply of the outermost wrapper would count its tokens twice.
+            return total_supply(env, asset.contract);
+        }
+        let wrapped_supply = total_supply(env, params.outermost())?;
+        params.unwrap(env, wrapped_supply)
+    }
+
+    fn validate_params(&self, params: &StrategyParams) -> Result<(), StrategyError> {
+        WrappedBalanceParams::parse(params).map(|_| ())
+    }
+}
@@ -0,0 +1,57 @@
+# Votes on a proposal of a DAO whose config holds an asset on a second chain. The plugin records
+# the snapshot block of every asset chain when the proposal is created, which the publisher reads
+# at the latest block to prove the asset against it.
+#
+# Expects the plugin on Sepolia (RPC_URL) and the asset on Base Sepolia (ASSET_RPC_URL), and the
+# voter's signature of the vote, see `vote_typed_data`.
+
+# Build the project
+echo "Building the project..."
+cargo build
+
+export ASSET_CHAIN_ID=84532
+export PROPOSAL_ID=${PROPOSAL_ID:-1}
+export DIRECTION=${DIRECTION:-2}
+export NONCE=${NONCE:-0}
+export DEADLINE=${DEADLINE:-4102444800}
+
+echo ""
+echo "----------------------------------------------------------------------"
+echo "|                                                                     |"
+echo "|  You should have exported you testnet private key for this to work  |"
+echo "|  You should have exported you testnet RPC_URL for this to work      |"
+echo "|  You should have exported the ASSET_RPC_URL of Base Sepolia         |"
+echo "|                                                                     |"
+echo "----------------------------------------------------------------------"
+echo ""
+
+# The snapshot of the asset chain is written when the proposal is created, after the snapshot
+# block of the proposal, so it is only visible at the latest block.
+ASSET_SNAPSHOT=$(cast call --rpc-url ${RPC_URL:?} ${CONFIG_CONTRACT:?} \
+    'assetSnapshots(uint256,uint64)(uint256,bytes32)' ${PROPOSAL_ID} ${ASSET_CHAIN_ID})
+echo "Asset chain snapshot: $ASSET_SNAPSHOT"
+if [ "$(echo "$ASSET_SNAPSHOT" | head -n 1)" == "0" ]; then
+    echo "Proposal $PROPOSAL_ID has no snapshot on chain $ASSET_CHAIN_ID."
+    exit 1
+fi
+
+# Publish the vote, proving the asset on the second chain at its snapshot block
+echo "Publishing the vote..."
+cargo run --bin publisher -- \
+    --chain-id=11155111 \
+    --rpc-url=${RPC_URL} \
+    --block-number=${PROVING_BLOCK_NUMBER:?} \
+    --asset-chain=${ASSET_CHAIN_ID}=${ASSET_RPC_URL:?} \
+    --voter-signature=${VOTER_SIGNATURE:?} \
+    --voter=${VOTER:?} \
+    --dao-address=${DAO_ADDRESS:?} \
+    --proposal-id=${PROPOSAL_ID} \
+    --direction=${DIRECTION} \
+    --balance=${BALANCE:?} \
+    --nonce=${NONCE} \
+    --deadline=${DEADLINE} \
+    --config-contract=${CONFIG_CONTRACT} \
+    --token=${TOYKEN_ADDRESS:?} \
+    --additional-delegation-data=${ADDITIONAL_DELEGATION_DATA:?} || exit 1
+
+echo "All operations completed successfully."
@@ -13,6 +13,8 @@ export DAO_ADDRESS=0x727B0141EF57f1AD53B9a8f63adFFD739Ea11fDf
 export PROPOSAL_ID=1
 export DIRECTION=2
 export BALANCE=900000000000000000
+export NONCE=0
+export DEADLINE=4102444800
 export ADDITIONAL_DELEGATION_DATA="8bF1e340055c7dE62F11229A149d3A1918de3d74"
 
 COUNTER_VALUE=$(cast call --rpc-url ${RPC_URL} ${COUNTER_ADDRESS:?} 'get()(uint256)')
@@ -43,6 +45,8 @@ cargo run --bin publisher -- \
     --proposal-id=${PROPOSAL_ID} \
     --direction=${DIRECTION} \
     --balance=${BALANCE} \
+    --nonce=${NONCE} \
+    --deadline=${DEADLINE} \
     --config-contract=${COUNTER_ADDRESS:?} \
     --token=${TOYKEN_ADDRESS:?} \
     --additional-delegation-data=${ADDITIONAL_DELEGATION_DATA:?}